@@ -0,0 +1,28 @@
+use common::{Day, Result};
+
+// Both parts of a day as plain functions, so that days of different types fit
+// in one list.
+pub type Parts = [fn(&str) -> Result<String>; 2];
+
+fn parts<D: Day>() -> Parts {
+    [D::part1, D::part2]
+}
+
+// Every day aoc run can call. aoc new adds new days above the marker, next to
+// their dependency in Cargo.toml.
+pub fn registered() -> Vec<(u32, Parts)> {
+    vec![
+        (1, parts::<day01::Solution>()),
+        (2, parts::<day02::Solution>()),
+        (3, parts::<day03::Solution>()),
+        (4, parts::<day04::Solution>()),
+        (5, parts::<day05::Solution>()),
+        (6, parts::<day06::Solution>()),
+        (7, parts::<day07::Solution>()),
+        (8, parts::<day08::Solution>()),
+        (9, parts::<day09::Solution>()),
+        (10, parts::<day10::Solution>()),
+        (11, parts::<day11::Solution>()),
+        // aoc new adds days above this line
+    ]
+}
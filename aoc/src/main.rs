@@ -0,0 +1,238 @@
+use common::Result;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+mod days;
+
+// Files of a new day, relative to its directory. "{day}" is replaced with the
+// name of the day, like "day12".
+const TEMPLATE: [(&str, &str); 7] = [
+    ("Cargo.toml", include_str!("../template/Cargo.toml")),
+    ("build.rs", include_str!("../template/build.rs")),
+    ("src/lib.rs", include_str!("../template/lib.rs")),
+    ("src/main.rs", include_str!("../template/main.rs")),
+    ("examples/manifest", include_str!("../template/manifest")),
+    // slots for the example from the puzzle text and the real input
+    ("examples/example.txt", ""),
+    ("input.txt", ""),
+];
+
+fn main() -> Result<()> {
+    // the days live next to this crate
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .ok_or("repository root not found")?;
+
+    // first arg is the name of the program
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["new", day] => {
+            let dir = new_day(root, parse_day(day)?)?;
+            println!("created {}", dir.display());
+            Ok(())
+        }
+        ["run", day] => run_day(root, parse_day(day)?, None),
+        ["run", day, path] => run_day(root, parse_day(day)?, Some(path)),
+        _ => Err("usage: aoc new <day> | aoc run <day> [input]".into()),
+    }
+}
+
+fn parse_day(src: &str) -> Result<u32> {
+    let src = src.strip_prefix("day").unwrap_or(src);
+    match src.parse() {
+        Ok(day @ 1..=25) => Ok(day),
+        _ => Err(format!("expected a day from 1 to 25, got {src:?}").into()),
+    }
+}
+
+fn day_name(day: u32) -> String {
+    format!("day{day:02}")
+}
+
+// Creates the directory of a new day from the template and registers the day
+// in aoc, so that the next aoc run can call it.
+fn new_day(root: &Path, day: u32) -> Result<PathBuf> {
+    let name = day_name(day);
+    let dir = root.join(&name);
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()).into());
+    }
+    // both are changed before anything is written, so that a failure doesn't
+    // leave a day behind half registered
+    let manifest_path = root.join("aoc/Cargo.toml");
+    let days_path = root.join("aoc/src/days.rs");
+    let manifest = add_dependency(&fs::read_to_string(&manifest_path)?, &name)?;
+    let days = add_registration(&fs::read_to_string(&days_path)?, day)?;
+
+    for (path, contents) in TEMPLATE {
+        let path = dir.join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, contents.replace("{day}", &name))?;
+    }
+    fs::write(manifest_path, manifest)?;
+    fs::write(days_path, days)?;
+    Ok(dir)
+}
+
+// adds the day as the last line of the [dependencies] table
+fn add_dependency(manifest: &str, name: &str) -> Result<String> {
+    let mut lines: Vec<&str> = manifest.lines().collect();
+    let header = lines
+        .iter()
+        .position(|line| line.trim() == "[dependencies]")
+        .ok_or("[dependencies] not found in aoc/Cargo.toml")?;
+    let end = lines[header + 1..]
+        .iter()
+        .position(|line| line.trim().is_empty() || line.starts_with('['))
+        .map_or(lines.len(), |i| header + 1 + i);
+    let dependency = format!("{name} = {{ path = \"../{name}\" }}");
+    lines.insert(end, &dependency);
+    Ok(lines.join("\n") + "\n")
+}
+
+const REGISTRATION_MARKER: &str = "// aoc new adds days above this line";
+
+fn add_registration(days: &str, day: u32) -> Result<String> {
+    let marker = days
+        .find(REGISTRATION_MARKER)
+        .ok_or("marker not found in aoc/src/days.rs")?;
+    let line_start = days[..marker].rfind('\n').map_or(0, |i| i + 1);
+    let line = format!("        ({day}, parts::<{}::Solution>()),\n", day_name(day));
+    Ok(format!(
+        "{}{line}{}",
+        &days[..line_start],
+        &days[line_start..]
+    ))
+}
+
+// Calls both parts of the day through common::Day, on the input.txt of the day
+// unless another input is given.
+fn run_day(root: &Path, day: u32, path: Option<&str>) -> Result<()> {
+    let name = day_name(day);
+    let [part1, part2] = days::registered()
+        .into_iter()
+        .find(|(registered, _)| *registered == day)
+        .map(|(_, parts)| parts)
+        .ok_or_else(|| format!("{name} not found, create it with: aoc new {day}"))?;
+    let path = match path {
+        Some(path) => PathBuf::from(path),
+        None => root.join(&name).join("input.txt"),
+    };
+    let input = common::read_input(&path)?;
+
+    println!("{}", part1(&input)?);
+    println!("{}", part2(&input)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_day_test() -> Result<()> {
+        assert_eq!(parse_day("12")?, 12);
+        assert_eq!(parse_day("day07")?, 7);
+        for src in ["0", "26", "day", "twelve", "-1"] {
+            assert!(parse_day(src).is_err(), "{src}");
+        }
+        assert_eq!(day_name(7), "day07");
+
+        Ok(())
+    }
+
+    #[test]
+    fn new_day_test() -> Result<()> {
+        let root = env::temp_dir().join(format!("aoc-new-day-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        // registering needs the files of aoc itself
+        assert!(new_day(&root, 12).is_err());
+        assert!(!root.join("day12").exists());
+        fs::create_dir_all(root.join("aoc/src"))?;
+        fs::write(root.join("aoc/Cargo.toml"), include_str!("../Cargo.toml"))?;
+        fs::write(root.join("aoc/src/days.rs"), include_str!("days.rs"))?;
+
+        let dir = new_day(&root, 12)?;
+        assert_eq!(dir, root.join("day12"));
+        let manifest = fs::read_to_string(dir.join("Cargo.toml"))?;
+        assert!(manifest.contains("name = \"day12\""), "{manifest}");
+        for (path, _) in TEMPLATE {
+            assert!(dir.join(path).is_file(), "{path}");
+        }
+        let examples = fs::read_to_string(dir.join("examples/manifest"))?;
+        assert_eq!(common::examples::parse_manifest(&examples)?.len(), 1);
+        let main = fs::read_to_string(dir.join("src/main.rs"))?;
+        assert!(main.contains("day12::run()"), "{main}");
+        let manifest = fs::read_to_string(root.join("aoc/Cargo.toml"))?;
+        assert!(
+            manifest.contains("day12 = { path = \"../day12\" }\n"),
+            "{manifest}"
+        );
+        let days = fs::read_to_string(root.join("aoc/src/days.rs"))?;
+        assert!(
+            days.contains("(12, parts::<day12::Solution>()),\n"),
+            "{days}"
+        );
+        // never overwrites a day
+        assert!(new_day(&root, 12).is_err());
+
+        fs::remove_dir_all(&root)?;
+        Ok(())
+    }
+
+    #[test]
+    fn add_dependency_test() -> Result<()> {
+        let manifest =
+            "[package]\nname = \"aoc\"\n\n[dependencies]\ncommon = \"1\"\n\n[dev-dependencies]\n";
+        let expected = concat!(
+            "[package]\nname = \"aoc\"\n\n[dependencies]\ncommon = \"1\"\n",
+            "day12 = { path = \"../day12\" }\n",
+            "\n[dev-dependencies]\n",
+        );
+        assert_eq!(add_dependency(manifest, "day12")?, expected);
+        assert_eq!(
+            add_dependency("[dependencies]", "day01")?,
+            "[dependencies]\nday01 = { path = \"../day01\" }\n"
+        );
+        assert!(add_dependency("[package]\n", "day12").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn add_registration_test() -> Result<()> {
+        let days = "vec![\n        (1, parts::<day01::Solution>()),\n        // aoc new adds days above this line\n    ]\n";
+        let expected = concat!(
+            "vec![\n",
+            "        (1, parts::<day01::Solution>()),\n",
+            "        (12, parts::<day12::Solution>()),\n",
+            "        // aoc new adds days above this line\n",
+            "    ]\n",
+        );
+        assert_eq!(add_registration(days, 12)?, expected);
+        assert!(add_registration("vec![]", 12).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn registered_test() -> Result<()> {
+        let registered = days::registered();
+        for (i, (day, _)) in registered.iter().enumerate() {
+            assert!(parse_day(&day.to_string()).is_ok());
+            assert!(
+                registered[..i].iter().all(|(other, _)| other != day),
+                "{day}"
+            );
+        }
+        let (_, [part1, part2]) = registered[0];
+        let input = common::normalize(include_str!("../../day01/examples/example.txt"));
+        assert_eq!(part1(&input)?, "24000");
+        assert_eq!(part2(&input)?, "45000");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use common::Result;
+use std::env;
+
+// everything the binary does, main.rs only calls this
+pub fn run() -> Result<()> {
+    // first arg is the name of the program
+    let path = match env::args().nth(1) {
+        Some(path) => path,
+        None => String::from("input.txt"),
+    };
+    let input = common::read_input(&path)?;
+
+    println!("{}", part1(&input)?);
+    println!("{}", part2(&input)?);
+    Ok(())
+}
+
+// both parts, for aoc run
+pub struct Solution;
+
+impl common::Day for Solution {
+    fn part1(input: &str) -> Result<String> {
+        part1(input)
+    }
+
+    fn part2(input: &str) -> Result<String> {
+        part2(input)
+    }
+}
+
+fn part1(_input: &str) -> Result<String> {
+    Err("not implemented".into())
+}
+
+fn part2(_input: &str) -> Result<String> {
+    Err("not implemented".into())
+}
+
+#[cfg(test)]
+mod tests {
+    // example_* tests, generated by build.rs from examples/manifest
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+}
@@ -0,0 +1,220 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use crate::Result;
+
+// One line of examples/manifest: the example file and the expected answers of
+// both parts, None where the answer is "-" (not known or not solved yet).
+#[derive(Debug, PartialEq)]
+pub struct Example {
+    pub file: String,
+    pub answers: [Option<String>; 2],
+}
+
+// Lines like "example.txt 24000 45000". Empty lines and lines starting with
+// "#" are skipped.
+pub fn parse_manifest(src: &str) -> Result<Vec<Example>> {
+    let mut examples: Vec<Example> = Vec::new();
+    for (i, line) in src.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let [file, part1, part2] = words[..] else {
+            return Err(format!(
+                "line {}: expected \"file part1 part2\", got {line:?}",
+                i + 1
+            )
+            .into());
+        };
+        let answer = |word: &str| (word != "-").then(|| word.to_string());
+        examples.push(Example {
+            file: file.to_string(),
+            answers: [answer(part1), answer(part2)],
+        });
+    }
+    Ok(examples)
+}
+
+// One test per example and known answer, and one per example that checks the
+// known answers of its messy variants. To be included in the tests module of
+// a day, so the parts are found in super.
+pub fn generate_tests(examples_dir: &Path, examples: &[Example]) -> String {
+    let mut code = String::new();
+    for example in examples {
+        let path = examples_dir.join(&example.file);
+        let name = test_name(&example.file);
+        for (part, answer) in (1..).zip(&example.answers) {
+            let Some(answer) = answer else {
+                continue;
+            };
+            code += &format!(
+                concat!(
+                    "#[test]\n",
+                    "fn {name}_part{part}() -> common::Result<()> {{\n",
+                    "    let input = common::normalize(include_str!({path:?}));\n",
+                    "    assert_eq!(super::part{part}(&input)?, {answer:?});\n",
+                    "    Ok(())\n",
+                    "}}\n\n",
+                ),
+                name = name,
+                part = part,
+                path = path,
+                answer = answer,
+            );
+        }
+        // the same answers when the example is saved with other line endings,
+        // a byte order mark or trailing whitespace
+        if example.answers.iter().all(Option::is_none) {
+            continue;
+        }
+        code += &format!(
+            concat!(
+                "#[test]\n",
+                "fn {name}_messy() -> common::Result<()> {{\n",
+                "    for input in common::input::messy_variants(include_str!({path:?})) {{\n",
+                "        let input = common::normalize(&input);\n",
+            ),
+            name = name,
+            path = path,
+        );
+        for (part, answer) in (1..).zip(&example.answers) {
+            if let Some(answer) = answer {
+                code += &format!("        assert_eq!(super::part{part}(&input)?, {answer:?});\n");
+            }
+        }
+        code += "    }\n    Ok(())\n}\n\n";
+    }
+    code
+}
+
+// "larger-example.txt" -> "example_larger_example"
+fn test_name(file: &str) -> String {
+    let stem = file.strip_suffix(".txt").unwrap_or(file);
+    let stem: String = stem
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | '0'..='9' => c,
+            'A'..='Z' => c.to_ascii_lowercase(),
+            _ => '_',
+        })
+        .collect();
+    match stem.strip_prefix("example") {
+        Some(_) => stem,
+        None => format!("example_{stem}"),
+    }
+}
+
+// For the build script of a day. Reads examples/manifest and writes the tests
+// to examples.rs in OUT_DIR. Every .txt file in examples has to be in the
+// manifest, so a forgotten answer doesn't go unnoticed.
+pub fn build() -> Result<()> {
+    let examples_dir = Path::new(&env::var("CARGO_MANIFEST_DIR")?).join("examples");
+    println!("cargo:rerun-if-changed={}", examples_dir.display());
+
+    let manifest_path = examples_dir.join("manifest");
+    let examples = parse_manifest(&fs::read_to_string(&manifest_path)?)?;
+    for entry in fs::read_dir(&examples_dir)? {
+        let file = entry?.file_name().to_string_lossy().into_owned();
+        if file.ends_with(".txt") && !examples.iter().any(|e| e.file == file) {
+            return Err(format!("{file} is missing from {}", manifest_path.display()).into());
+        }
+    }
+    for example in &examples {
+        if !examples_dir.join(&example.file).is_file() {
+            return Err(format!("{} listed in the manifest doesn't exist", example.file).into());
+        }
+    }
+
+    let out_path = Path::new(&env::var("OUT_DIR")?).join("examples.rs");
+    fs::write(out_path, generate_tests(&examples_dir, &examples))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_manifest_test() -> Result<()> {
+        let manifest = concat!(
+            "# file part1 part2\n",
+            "example.txt 24000 45000\n",
+            "\n",
+            "larger.txt  88    -\n",
+        );
+        assert_eq!(
+            parse_manifest(manifest)?,
+            vec![
+                Example {
+                    file: String::from("example.txt"),
+                    answers: [Some(String::from("24000")), Some(String::from("45000"))],
+                },
+                Example {
+                    file: String::from("larger.txt"),
+                    answers: [Some(String::from("88")), None],
+                },
+            ]
+        );
+        assert!(parse_manifest("example.txt 1").is_err());
+        assert!(parse_manifest("example.txt 1 2 3").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn generate_tests_test() -> Result<()> {
+        let examples = parse_manifest("example.txt CMZ MCD\nLarger-2.txt - 36")?;
+        let code = generate_tests(Path::new("/day/examples"), &examples);
+        let expected = concat!(
+            "#[test]\n",
+            "fn example_part1() -> common::Result<()> {\n",
+            "    let input = common::normalize(include_str!(\"/day/examples/example.txt\"));\n",
+            "    assert_eq!(super::part1(&input)?, \"CMZ\");\n",
+            "    Ok(())\n",
+            "}\n",
+            "\n",
+            "#[test]\n",
+            "fn example_part2() -> common::Result<()> {\n",
+            "    let input = common::normalize(include_str!(\"/day/examples/example.txt\"));\n",
+            "    assert_eq!(super::part2(&input)?, \"MCD\");\n",
+            "    Ok(())\n",
+            "}\n",
+            "\n",
+            "#[test]\n",
+            "fn example_messy() -> common::Result<()> {\n",
+            "    for input in common::input::messy_variants(include_str!(\"/day/examples/example.txt\")) {\n",
+            "        let input = common::normalize(&input);\n",
+            "        assert_eq!(super::part1(&input)?, \"CMZ\");\n",
+            "        assert_eq!(super::part2(&input)?, \"MCD\");\n",
+            "    }\n",
+            "    Ok(())\n",
+            "}\n",
+            "\n",
+            "#[test]\n",
+            "fn example_larger_2_part2() -> common::Result<()> {\n",
+            "    let input = common::normalize(include_str!(\"/day/examples/Larger-2.txt\"));\n",
+            "    assert_eq!(super::part2(&input)?, \"36\");\n",
+            "    Ok(())\n",
+            "}\n",
+            "\n",
+            "#[test]\n",
+            "fn example_larger_2_messy() -> common::Result<()> {\n",
+            "    for input in common::input::messy_variants(include_str!(\"/day/examples/Larger-2.txt\")) {\n",
+            "        let input = common::normalize(&input);\n",
+            "        assert_eq!(super::part2(&input)?, \"36\");\n",
+            "    }\n",
+            "    Ok(())\n",
+            "}\n",
+            "\n",
+        );
+        // nothing to compare against
+        let examples = parse_manifest("unsolved.txt - -")?;
+        assert_eq!(generate_tests(Path::new("/day/examples"), &examples), "");
+        assert_eq!(code, expected);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,144 @@
+use std::env;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::Rng;
+
+// Numbers that tend to break parsers and arithmetic.
+const NUMBERS: [&str; 12] = [
+    "0",
+    "1",
+    "-1",
+    "255",
+    "65535",
+    "2147483647",
+    "2147483648",
+    "4294967295",
+    "4294967296",
+    "9223372036854775807",
+    "18446744073709551616",
+    "99999999999999999999999",
+];
+
+// Characters that mean something to at least one of the parsers.
+const CHARACTERS: &str = "0123456789abcxyzABCXYZ \n\t-+*,:$/.()[]";
+
+// Small fuzzer that runs offline on stable Rust, in place of cargo-fuzz.
+// Mutates the seed inputs at random and passes every mutation to target,
+// which must not panic. Arithmetic overflows panic in test builds too, so
+// those are caught as well. FUZZ_ITERATIONS sets the amount of inputs
+// (1000 by default) and FUZZ_SEED picks different ones:
+//   FUZZ_ITERATIONS=1000000 FUZZ_SEED=7 cargo test fuzz
+pub fn fuzz(seeds: &[&str], target: impl Fn(&str)) {
+    let iterations: u64 = env_number("FUZZ_ITERATIONS").unwrap_or(1000);
+    let mut rng = Rng::new(env_number("FUZZ_SEED").unwrap_or(0));
+
+    for _ in 0..iterations {
+        let input = mutate(&mut rng, seeds);
+        if panic::catch_unwind(AssertUnwindSafe(|| target(&input))).is_err() {
+            panic!("fuzz target panicked on input {input:?}");
+        }
+    }
+}
+
+fn env_number(name: &str) -> Option<u64> {
+    env::var(name).ok()?.parse().ok()
+}
+
+fn mutate(rng: &mut Rng, seeds: &[&str]) -> String {
+    let mut chars: Vec<char> = rng.choose(seeds).chars().collect();
+    for _ in 0..rng.range(1..=4) {
+        let position = rng.below(chars.len() as u64 + 1) as usize;
+        let rest = chars.len() - position;
+        match rng.below(7) {
+            0 => {
+                let alphabet: Vec<char> = CHARACTERS.chars().collect();
+                chars.insert(position, *rng.choose(&alphabet));
+            }
+            1 => {
+                let len = rng.range(0..=rest.min(8) as i64) as usize;
+                chars.drain(position..position + len);
+            }
+            2 => {
+                // copy a piece somewhere else
+                let len = rng.range(0..=rest.min(16) as i64) as usize;
+                let piece: Vec<char> = chars[position..position + len].to_vec();
+                let target = rng.below(chars.len() as u64 + 1) as usize;
+                chars.splice(target..target, piece);
+            }
+            3 => replace_number(rng, &mut chars),
+            4 => {
+                let mut lines: Vec<String> = chars
+                    .iter()
+                    .collect::<String>()
+                    .lines()
+                    .map(String::from)
+                    .collect();
+                if !lines.is_empty() {
+                    let i = rng.below(lines.len() as u64) as usize;
+                    let j = rng.below(lines.len() as u64) as usize;
+                    lines.swap(i, j);
+                }
+                chars = lines.join("\n").chars().collect();
+            }
+            5 => chars.truncate(position),
+            _ => {
+                // glue the start of this input to the end of another one
+                let other: Vec<char> = rng.choose(seeds).chars().collect();
+                let start = rng.below(other.len() as u64 + 1) as usize;
+                chars.truncate(position);
+                chars.extend(&other[start..]);
+            }
+        }
+    }
+    chars.into_iter().collect()
+}
+
+// swaps one run of digits for one of NUMBERS
+fn replace_number(rng: &mut Rng, chars: &mut Vec<char>) {
+    let mut runs: Vec<(usize, usize)> = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i].is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && chars[i].is_ascii_digit() {
+                i += 1;
+            }
+            runs.push((start, i));
+        } else {
+            i += 1;
+        }
+    }
+    if runs.is_empty() {
+        return;
+    }
+    let (start, end) = *rng.choose(&runs);
+    chars.splice(start..end, rng.choose(&NUMBERS).chars());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mutate_test() {
+        let mut rng = Rng::new(0);
+        let seeds = ["move 1 from 2 to 3", ""];
+        let mutations: Vec<String> = (0..100).map(|_| mutate(&mut rng, &seeds)).collect();
+        assert!(mutations.iter().any(|m| m.is_empty()));
+        assert!(mutations.iter().any(|m| m.len() > seeds[0].len()));
+        assert!(mutations
+            .iter()
+            .any(|m| NUMBERS[5..].iter().any(|n| m.contains(n))));
+    }
+
+    #[test]
+    #[should_panic(expected = "fuzz target panicked")]
+    fn fuzz_test() {
+        // "1000" can become "4294967296"
+        fuzz(&["1000"], |input| {
+            if let Ok(x) = input.parse::<u64>() {
+                let _ = x as u32 + u32::try_from(x).unwrap_or(u32::MAX);
+            }
+        });
+    }
+}
@@ -0,0 +1,246 @@
+use std::ops::{Index, IndexMut};
+
+use crate::Result;
+
+// (dx, dy) steps, y grows downwards like the lines of the input
+pub const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+pub const DIAGONAL: [(isize, isize); 4] = [(1, -1), (1, 1), (-1, 1), (-1, -1)];
+
+// Rectangular grid stored row by row. Positions are (x, y) pairs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    values: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, values: Vec<T>) -> Result<Grid<T>> {
+        if width.checked_mul(height) != Some(values.len()) {
+            return Err(
+                format!("{} values don't fill a {width}x{height} grid", values.len()).into(),
+            );
+        }
+        Ok(Grid {
+            width,
+            height,
+            values,
+        })
+    }
+
+    // Parses lines of characters into a grid, every line is one row. All rows
+    // need to be equally wide.
+    pub fn parse_chars(input: &str, mut parse: impl FnMut(char) -> Result<T>) -> Result<Grid<T>> {
+        let mut width = None;
+        let mut height = 0;
+        let mut values: Vec<T> = Vec::new();
+
+        for (y, line) in input.lines().enumerate() {
+            let before = values.len();
+            for c in line.chars() {
+                values.push(parse(c)?);
+            }
+            let row_width = values.len() - before;
+            match width {
+                None => width = Some(row_width),
+                Some(w) if w != row_width => {
+                    return Err(
+                        format!("row {} has {row_width} columns, expected {w}", y + 1).into(),
+                    )
+                }
+                _ => (),
+            }
+            height += 1;
+        }
+
+        match width {
+            Some(width) if width > 0 => Grid::new(width, height, values),
+            _ => Err("empty grid".into()),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x < self.width && y < self.height {
+            self.values.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            self.values.get_mut(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    // position one (dx, dy) step away, None if that falls off the grid
+    pub fn offset(
+        &self,
+        (x, y): (usize, usize),
+        (dx, dy): (isize, isize),
+    ) -> Option<(usize, usize)> {
+        let x = x.checked_add_signed(dx)?;
+        let y = y.checked_add_signed(dy)?;
+        if x < self.width && y < self.height {
+            Some((x, y))
+        } else {
+            None
+        }
+    }
+
+    // all positions row by row
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn neighbors4(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        ORTHOGONAL
+            .into_iter()
+            .filter_map(move |direction| self.offset(position, direction))
+    }
+
+    pub fn neighbors8(
+        &self,
+        position: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        ORTHOGONAL
+            .into_iter()
+            .chain(DIAGONAL)
+            .filter_map(move |direction| self.offset(position, direction))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.values.chunks(self.width.max(1))
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        self.rows().nth(y)
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        // an out of bounds x gives an empty column instead of wrapping around
+        let skip = if x < self.width { x } else { self.values.len() };
+        self.values.iter().skip(skip).step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            values: self.values.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Grid<T> {
+        Grid {
+            width,
+            height,
+            values: vec![value; width * height],
+        }
+    }
+
+    // rows become columns
+    pub fn transpose(&self) -> Grid<T> {
+        let values = self.columns().flat_map(|column| column.cloned()).collect();
+        Grid {
+            width: self.height,
+            height: self.width,
+            values,
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y).expect("position out of bounds")
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        self.get_mut(x, y).expect("position out of bounds")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Result<Grid<u32>> {
+        Grid::parse_chars(input, |c| c.to_digit(10).ok_or("not a digit".into()))
+    }
+
+    #[test]
+    fn parse_test() -> Result<()> {
+        let grid = digits("123\n456")?;
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid.get(2, 1), Some(&6));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid[(0, 1)], 4);
+
+        assert!(digits("123\n45").is_err());
+        assert!(digits("12a").is_err());
+        assert!(digits("").is_err());
+        // width is counted in characters, not bytes
+        let grid = Grid::parse_chars("äö\nüß", Ok)?;
+        assert_eq!(grid.width(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn neighbors_test() -> Result<()> {
+        let grid = digits("123\n456\n789")?;
+        let mut corner: Vec<_> = grid.neighbors4((0, 0)).collect();
+        corner.sort();
+        assert_eq!(corner, vec![(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbors4((1, 1)).count(), 4);
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbors8((2, 2)).count(), 3);
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rows_and_columns_test() -> Result<()> {
+        let grid = digits("123\n456")?;
+        assert_eq!(grid.row(1), Some(&[4, 5, 6][..]));
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![2, 5]);
+        assert_eq!(grid.column(3).count(), 0);
+        assert_eq!(grid.columns().count(), 3);
+
+        let transposed = grid.transpose();
+        assert_eq!(transposed, digits("14\n25\n36")?);
+        assert_eq!(transposed.transpose(), grid);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::io::BufRead;
+use std::iter;
+use std::path::Path;
+
+use crate::Result;
+
+// Puts the input into the one shape all the parsers expect, whatever editor
+// or OS it was saved with: no byte order mark, "\n" line endings, no
+// whitespace at the end of a line and no empty lines at the end.
+pub fn normalize(input: &str) -> String {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let lines: Vec<&str> = input.split('\n').flat_map(split_line).collect();
+    let end = lines
+        .iter()
+        .rposition(|line| !line.is_empty())
+        .map_or(0, |i| i + 1);
+    lines[..end].join("\n")
+}
+
+// reads the whole file and normalizes it
+pub fn read_input(path: impl AsRef<Path>) -> Result<String> {
+    Ok(normalize(&fs::read_to_string(path)?))
+}
+
+// The lines normalize would make, for inputs too large to read at once.
+// Empty lines at the end are kept, since they look the same as empty lines
+// in the middle until the end is reached.
+pub fn lines(mut reader: impl BufRead) -> impl Iterator<Item = Result<String>> {
+    let mut buffer = String::new();
+    let mut is_first = true;
+    let mut pending: VecDeque<String> = VecDeque::new();
+    iter::from_fn(move || loop {
+        if let Some(line) = pending.pop_front() {
+            return Some(Ok(line));
+        }
+        buffer.clear();
+        match reader.read_line(&mut buffer) {
+            Ok(0) => return None,
+            Ok(_) => (),
+            Err(e) => return Some(Err(e.into())),
+        }
+        let mut line = buffer.strip_suffix('\n').unwrap_or(&buffer);
+        // only the very start of the input can have a byte order mark
+        if is_first {
+            line = line.strip_prefix('\u{feff}').unwrap_or(line);
+            is_first = false;
+        }
+        pending.extend(split_line(line).map(String::from));
+    })
+}
+
+// One line split on "\n". "\r\n" and a lone "\r" (old Macs) both end a
+// line too.
+fn split_line(line: &str) -> impl Iterator<Item = &str> {
+    line.strip_suffix('\r')
+        .unwrap_or(line)
+        .split('\r')
+        .map(str::trim_end)
+}
+
+// The same input saved in ways normalize has to undo, for tests.
+pub fn messy_variants(input: &str) -> Vec<String> {
+    vec![
+        input.replace('\n', "\r\n"),
+        format!("\u{feff}{input}\n"),
+        input.replace('\n', "  \n") + "\t\n\n\n",
+        format!("\u{feff}{}\r\n\r\n", input.replace('\n', " \r\n")),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_test() {
+        assert_eq!(normalize("a\r\nb\r\n"), "a\nb");
+        assert_eq!(normalize("\u{feff}a\nb"), "a\nb");
+        assert_eq!(normalize("a  \n\tb\t\n\n\n"), "a\n\tb");
+        assert_eq!(normalize("a\rb\r"), "a\nb");
+        // empty lines in the middle separate groups in some days
+        assert_eq!(normalize("a\r\n\r\nb"), "a\n\nb");
+        assert_eq!(normalize("a\n \nb"), "a\n\nb");
+        // leading whitespace matters for day05's stacks
+        assert_eq!(normalize("    [D]    \n[N] [C]"), "    [D]\n[N] [C]");
+        assert_eq!(normalize(""), "");
+        assert_eq!(normalize("\r\n\n"), "");
+
+        for variant in messy_variants("a\n\nb c") {
+            assert_eq!(normalize(&variant), "a\n\nb c");
+        }
+    }
+
+    #[test]
+    fn read_input_test() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("read_input_test_{}.txt", std::process::id()));
+        let input = "    [D]\n[N] [C]\n\nmove 1 from 2 to 1";
+        for variant in messy_variants(input) {
+            fs::write(&path, &variant)?;
+            let result = read_input(&path);
+            fs::remove_file(&path)?;
+            assert_eq!(result?, input, "{variant:?}");
+        }
+        assert!(read_input(&path).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn lines_test() -> Result<()> {
+        let input = "a\n\n\u{feff}b c";
+        for variant in messy_variants(input).iter().map(String::as_str).chain([
+            "a\rb\r",
+            "a\r\rb",
+            "\u{feff}\u{feff}a",
+            "a\n\u{feff}",
+        ]) {
+            let lines: Vec<String> = lines(variant.as_bytes()).collect::<Result<_>>()?;
+            // normalize drops the empty lines at the end too
+            let end = lines
+                .iter()
+                .rposition(|line| !line.is_empty())
+                .map_or(0, |i| i + 1);
+            assert_eq!(lines[..end].join("\n"), normalize(variant), "{variant:?}");
+        }
+
+        Ok(())
+    }
+}
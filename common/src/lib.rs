@@ -0,0 +1,21 @@
+// Code shared between the days. Each day is still its own crate and pulls
+// this in as a path dependency.
+
+pub mod examples;
+pub mod fuzz;
+pub mod grid;
+pub mod input;
+pub mod random;
+
+pub use grid::Grid;
+pub use input::{normalize, read_input};
+pub use random::Rng;
+
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+// Both parts of a day, implemented by every day so that aoc can run any of
+// them. Answers are strings since some puzzles answer with letters.
+pub trait Day {
+    fn part1(input: &str) -> Result<String>;
+    fn part2(input: &str) -> Result<String>;
+}
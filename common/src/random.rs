@@ -0,0 +1,115 @@
+use std::env;
+use std::ops::RangeInclusive;
+
+use crate::Result;
+
+// Small seeded random number generator (SplitMix64). Good enough for making
+// up puzzle inputs, and the same seed gives the same input everywhere.
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // uniform in 0..n, n has to be positive
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "empty range");
+        // the high bits of the 128 bit product, close enough to uniform
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "empty range");
+        let span = end.abs_diff(start);
+        match span.checked_add(1) {
+            Some(n) => start.wrapping_add(self.below(n) as i64),
+            None => self.next_u64() as i64,
+        }
+    }
+
+    // true with the given probability in percent
+    pub fn percent(&mut self, percent: u64) -> bool {
+        self.below(100) < percent
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        // Fisher-Yates
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+// Looks for a "--generate=SEED,SIZE" argument. Days that support it print a
+// random input of roughly SIZE lines (or whatever SIZE means for that day)
+// instead of solving one.
+pub fn generate_flag() -> Result<Option<(u64, usize)>> {
+    match env::args().find_map(|arg| arg.strip_prefix("--generate=").map(String::from)) {
+        Some(value) => parse_generate(&value).map(Some),
+        None => Ok(None),
+    }
+}
+
+fn parse_generate(value: &str) -> Result<(u64, usize)> {
+    let (seed, size) = value
+        .split_once(',')
+        .ok_or_else(|| format!("expected --generate=SEED,SIZE, got {value:?}"))?;
+    Ok((seed.parse()?, size.parse()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rng_test() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+
+        let mut rng = Rng::new(7);
+        let mut seen = [false; 6];
+        for _ in 0..1000 {
+            let x = rng.range(-2..=3);
+            assert!((-2..=3).contains(&x));
+            seen[(x + 2) as usize] = true;
+        }
+        assert!(seen.iter().all(|x| *x));
+        assert_eq!(rng.range(5..=5), 5);
+        rng.range(i64::MIN..=i64::MAX);
+
+        let mut items = [1, 2, 3, 4, 5];
+        rng.shuffle(&mut items);
+        items.sort();
+        assert_eq!(items, [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn parse_generate_test() -> Result<()> {
+        assert_eq!(parse_generate("1,100")?, (1, 100));
+        assert!(parse_generate("1").is_err());
+        assert!(parse_generate("a,100").is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,4 @@
+// generates a test for every example listed in examples/manifest
+fn main() -> common::Result<()> {
+    common::examples::build()
+}
@@ -0,0 +1,459 @@
+use common::{Result, Rng};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::env;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+// everything the binary does, main.rs only calls this
+pub fn run() -> Result<()> {
+    // prints a random input instead of solving one
+    if let Some((seed, size)) = common::random::generate_flag()? {
+        println!("{}", generate(seed, size));
+        return Ok(());
+    }
+
+    // first arg is the name of the program
+    let path = match env::args().nth(1) {
+        Some(path) => path,
+        None => String::from("input.txt"),
+    };
+    // rest of the args are optional flags
+    let flags: Vec<String> = env::args().skip(2).collect();
+
+    // very large inventories don't fit in memory, so read them line by line
+    for flag in &flags {
+        if let Some(k) = flag.strip_prefix("--stream-top=") {
+            let reader = BufReader::new(File::open(&path)?);
+            for (id, total) in stream_top_k(reader, k.parse()?)? {
+                println!("elf #{id}: {total}");
+            }
+            return Ok(());
+        }
+    }
+
+    let input = common::read_input(&path)?;
+
+    println!("{}", part1(&input)?);
+    println!("{}", part2(&input)?);
+
+    if flags.iter().any(|f| f == "--stats") {
+        let mut top_count = 3;
+        for flag in &flags {
+            if let Some(n) = flag.strip_prefix("--top=") {
+                top_count = n.parse()?;
+            }
+        }
+        print_stats(&Inventory::parse(&input)?, top_count);
+    }
+    Ok(())
+}
+
+// both parts, for aoc run
+pub struct Solution;
+
+impl common::Day for Solution {
+    fn part1(input: &str) -> Result<String> {
+        part1(input)
+    }
+
+    fn part2(input: &str) -> Result<String> {
+        part2(input)
+    }
+}
+
+fn part1(input: &str) -> Result<String> {
+    let top = stream_top_k(input.as_bytes(), 1)?;
+    let max_total_calories = sum_totals(&top)?;
+
+    Ok(max_total_calories.to_string())
+}
+
+fn part2(input: &str) -> Result<String> {
+    let top = stream_top_k(input.as_bytes(), 3)?;
+    let sum_top_three = sum_totals(&top)?;
+
+    Ok(sum_top_three.to_string())
+}
+
+fn sum_totals(top: &[(usize, i32)]) -> Result<i32> {
+    top.iter()
+        .try_fold(0i32, |sum, (_, total)| sum.checked_add(*total))
+        .ok_or_else(|| "top elves carry too many calories together".into())
+}
+
+fn print_stats(inventory: &Inventory, top_count: usize) {
+    println!("elves: {}", inventory.elves.len());
+    for (id, total) in inventory.top(top_count) {
+        println!("elf #{id}: {total}");
+    }
+    println!("mean: {:.1}", inventory.mean());
+    println!("median: {:.1}", inventory.median());
+    print!("{}", inventory.histogram(10));
+}
+
+// Same as Inventory::parse followed by Inventory::top, but in one pass over
+// the input and without keeping more than k elves in memory.
+fn stream_top_k(reader: impl BufRead, k: usize) -> Result<Vec<(usize, i32)>> {
+    let mut top = TopK::new(k);
+    let mut id = 0;
+    let mut current_sum: Option<i32> = None;
+
+    // same lines as common::normalize makes of the whole input
+    for line in common::input::lines(reader) {
+        let line = line?;
+        if line.is_empty() {
+            // same as in Inventory::parse, empty lines in a row don't make empty elves
+            if let Some(sum) = current_sum.take() {
+                id += 1;
+                top.push(id, sum);
+            }
+        } else {
+            let calories: i32 = str::parse(&line)?;
+            let sum = current_sum.get_or_insert(0);
+            *sum = sum
+                .checked_add(calories)
+                .ok_or_else(|| too_many_calories(id + 1))?;
+        }
+    }
+    // the last elf doesn't need an empty line after it
+    if let Some(sum) = current_sum {
+        top.push(id + 1, sum);
+    }
+
+    Ok(top.into_sorted_vec())
+}
+
+fn too_many_calories(id: usize) -> Box<dyn std::error::Error> {
+    format!("elf #{id} carries too many calories").into()
+}
+
+// Keeps the k largest totals seen so far in a min-heap, so the smallest of
+// them is always on top and cheap to compare against the next total.
+struct TopK {
+    k: usize,
+    // ties go to the elf that came first, so smaller id counts as larger
+    heap: BinaryHeap<Reverse<(i32, Reverse<usize>)>>,
+}
+
+impl TopK {
+    fn new(k: usize) -> TopK {
+        TopK {
+            k,
+            heap: BinaryHeap::with_capacity(k + 1),
+        }
+    }
+
+    fn push(&mut self, id: usize, total: i32) {
+        let entry = Reverse((total, Reverse(id)));
+        if self.heap.len() < self.k {
+            self.heap.push(entry);
+        } else if let Some(mut smallest) = self.heap.peek_mut() {
+            // Reverse flips the comparison, so this means "larger than smallest"
+            if entry < *smallest {
+                *smallest = entry;
+            }
+        }
+    }
+
+    // largest first
+    fn into_sorted_vec(self) -> Vec<(usize, i32)> {
+        // sorted ascending by Reverse, which is descending by total
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((total, Reverse(id)))| (id, total))
+            .collect()
+    }
+}
+
+struct Elf {
+    // 1-based, in the order the elves appear in the input
+    id: usize,
+    items: Vec<i32>,
+}
+
+impl Elf {
+    fn total(&self) -> i32 {
+        self.items.iter().sum()
+    }
+}
+
+struct Inventory {
+    elves: Vec<Elf>,
+}
+
+impl Inventory {
+    fn parse(input: &str) -> Result<Inventory> {
+        let mut elves: Vec<Elf> = Vec::new();
+        let mut items: Vec<i32> = Vec::new();
+        let mut total: i32 = 0;
+
+        for line in input.lines() {
+            if line.is_empty() {
+                // several empty lines in a row don't make empty elves
+                if !items.is_empty() {
+                    elves.push(Elf {
+                        id: elves.len() + 1,
+                        items,
+                    });
+                    items = Vec::new();
+                    total = 0;
+                }
+            } else {
+                let calories: i32 = str::parse(line)?;
+                // checked here so that Elf::total can't overflow later
+                total = total
+                    .checked_add(calories)
+                    .ok_or_else(|| too_many_calories(elves.len() + 1))?;
+                items.push(calories);
+            }
+        }
+        // the last elf doesn't need an empty line after it
+        if !items.is_empty() {
+            elves.push(Elf {
+                id: elves.len() + 1,
+                items,
+            });
+        }
+
+        Ok(Inventory { elves })
+    }
+
+    // (elf id, total calories) of the n elves carrying the most
+    fn top(&self, n: usize) -> Vec<(usize, i32)> {
+        let mut top = TopK::new(n);
+        for elf in &self.elves {
+            top.push(elf.id, elf.total());
+        }
+        top.into_sorted_vec()
+    }
+
+    fn mean(&self) -> f64 {
+        if self.elves.is_empty() {
+            return 0.0;
+        }
+        let sum: f64 = self.elves.iter().map(|e| e.total() as f64).sum();
+        sum / self.elves.len() as f64
+    }
+
+    fn median(&self) -> f64 {
+        let mut totals: Vec<i32> = self.elves.iter().map(Elf::total).collect();
+        totals.sort();
+        let middle = totals.len() / 2;
+        match totals.len() {
+            0 => 0.0,
+            len if len % 2 == 0 => (totals[middle - 1] as f64 + totals[middle] as f64) / 2.0,
+            _ => totals[middle] as f64,
+        }
+    }
+
+    // text histogram of the totals split into equally wide buckets
+    fn histogram(&self, bucket_count: usize) -> String {
+        let totals: Vec<i32> = self.elves.iter().map(Elf::total).collect();
+        let (min, max) = match (totals.iter().min(), totals.iter().max()) {
+            (Some(min), Some(max)) => (*min as i64, *max as i64),
+            _ => return String::new(),
+        };
+        let bucket_count = bucket_count.max(1) as i64;
+        // round up so that the max ends up in the last bucket
+        let bucket_width = ((max - min) / bucket_count + 1).max(1);
+
+        let mut counts: Vec<usize> = vec![0; bucket_count as usize];
+        for total in totals {
+            let bucket = ((total as i64 - min) / bucket_width).min(bucket_count - 1);
+            counts[bucket as usize] += 1;
+        }
+
+        let largest = counts.iter().max().copied().unwrap_or(0).max(1);
+        let mut result = String::new();
+        for (i, count) in counts.iter().enumerate() {
+            let start = min + i as i64 * bucket_width;
+            let end = start + bucket_width - 1;
+            // widest bar is 50 characters
+            let bar = "#".repeat(count * 50 / largest);
+            result += &format!("{start:>7} - {end:>7} | {bar} {count}\n");
+        }
+        result
+    }
+}
+
+// size is the amount of elves
+fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut elves: Vec<String> = Vec::new();
+    for _ in 0..size {
+        let items: Vec<String> = (0..rng.range(1..=10))
+            .map(|_| rng.range(1000..=60000).to_string())
+            .collect();
+        elves.push(items.join("\n"));
+    }
+    elves.join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("../examples/example.txt");
+
+    // example_* tests, generated by build.rs from examples/manifest
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+
+    #[test]
+    fn inventory_test() -> Result<()> {
+        let inventory = Inventory::parse(INPUT)?;
+        assert_eq!(inventory.elves.len(), 5);
+        // last elf has no empty line after it
+        assert_eq!(inventory.elves[4].items, vec![10000]);
+        assert_eq!(
+            inventory.top(5),
+            vec![(4, 24000), (3, 11000), (5, 10000), (1, 6000), (2, 4000)]
+        );
+        assert_eq!(stream_top_k(INPUT.as_bytes(), 5)?, inventory.top(5));
+        assert_eq!(inventory.mean(), 11000.0);
+        assert_eq!(inventory.median(), 10000.0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn stream_top_k_test() -> Result<()> {
+        for input in [INPUT, "", "1\n\n2\n\n\n\n2\n\n1", "7"] {
+            let inventory = Inventory::parse(input)?;
+            let elf_count = inventory.elves.len();
+            for k in [0, 1, 2, 3, elf_count, elf_count + 1, 100] {
+                let top = stream_top_k(input.as_bytes(), k)?;
+                assert_eq!(top, inventory.top(k), "{input:?} k={k}");
+                assert_eq!(top.len(), k.min(elf_count));
+            }
+        }
+        // ties go to the elf that came first
+        assert_eq!(
+            stream_top_k("1\n\n2\n\n\n\n2\n\n1".as_bytes(), 3)?,
+            vec![(2, 2), (3, 2), (1, 1)]
+        );
+        assert!(stream_top_k("1\n\nx".as_bytes(), 0).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn stats_test() -> Result<()> {
+        // even number of elves, the median is between the middle two
+        let inventory = Inventory::parse("1\n\n2\n\n3\n\n4\n6")?;
+        assert_eq!(inventory.elves.len(), 4);
+        assert_eq!(inventory.elves[3].total(), 10);
+        assert_eq!(inventory.mean(), 4.0);
+        assert_eq!(inventory.median(), 2.5);
+
+        let empty = Inventory::parse("")?;
+        assert!(empty.elves.is_empty());
+        assert_eq!(empty.mean(), 0.0);
+        assert_eq!(empty.median(), 0.0);
+        assert!(empty.top(3).is_empty());
+        assert_eq!(empty.histogram(10), "");
+
+        assert!(Inventory::parse("1\nx").is_err());
+        assert!(Inventory::parse("2147483647\n1").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn histogram_test() -> Result<()> {
+        let inventory = Inventory::parse(INPUT)?;
+        let full = "#".repeat(50);
+        let expected = format!(
+            concat!(
+                "   4000 -    8000 | {full} 2\n",
+                "   8001 -   12001 | {full} 2\n",
+                "  12002 -   16002 |  0\n",
+                "  16003 -   20003 |  0\n",
+                "  20004 -   24004 | ######################### 1\n",
+            ),
+            full = full
+        );
+        assert_eq!(inventory.histogram(5), expected);
+
+        // the largest total ends up in the last bucket, not past it
+        let histogram = inventory.histogram(10);
+        assert_eq!(histogram.lines().count(), 10);
+        assert!(histogram.ends_with("| ######################### 1\n"));
+        // every total in one bucket when they are all the same
+        let same = Inventory::parse("5\n\n5")?;
+        assert_eq!(same.histogram(0), format!("      5 -       5 | {full} 2\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn stream_top_k_messy_test() -> Result<()> {
+        // --stream-top reads the file without normalizing it first
+        for input in common::input::messy_variants(INPUT) {
+            assert_eq!(
+                stream_top_k(input.as_bytes(), 3)?,
+                stream_top_k(INPUT.as_bytes(), 3)?
+            );
+        }
+        // a byte order mark is only skipped at the very start
+        assert!(stream_top_k("1\n\u{feff}2".as_bytes(), 3).is_err());
+        // old Mac line endings
+        assert_eq!(
+            stream_top_k("1\r2\r\r3\r".as_bytes(), 3)?,
+            vec![(1, 3), (2, 3)]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn generate_test() -> Result<()> {
+        let input = generate(1, 100);
+        assert_eq!(input, generate(1, 100));
+        assert_ne!(input, generate(2, 100));
+        part1(&input)?;
+        part2(&input)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn top_k_test() -> Result<()> {
+        for seed in 0..50 {
+            let input = generate(seed, seed as usize);
+            let inventory = Inventory::parse(&input)?;
+            // the obvious way: sort everything
+            let mut totals: Vec<(usize, i32)> =
+                inventory.elves.iter().map(|e| (e.id, e.total())).collect();
+            totals.sort_by_key(|(id, total)| (Reverse(*total), *id));
+            for k in [0, 1, 3, 10] {
+                let expected: Vec<_> = totals.iter().copied().take(k).collect();
+                assert_eq!(inventory.top(k), expected);
+                assert_eq!(stream_top_k(input.as_bytes(), k)?, expected);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn fuzz_stream_top_k_test() {
+        common::fuzz::fuzz(&[INPUT, &generate(0, 10)], |input| {
+            let _ = part1(input);
+            let _ = part2(input);
+        });
+    }
+
+    #[test]
+    fn fuzz_inventory_test() {
+        common::fuzz::fuzz(&[INPUT, &generate(0, 10)], |input| {
+            if let Ok(inventory) = Inventory::parse(input) {
+                inventory.top(3);
+                inventory.mean();
+                inventory.median();
+                inventory.histogram(10);
+            }
+        });
+    }
+}
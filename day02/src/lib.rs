@@ -0,0 +1,738 @@
+use common::{Result, Rng};
+use std::env;
+
+// everything the binary does, main.rs only calls this
+pub fn run() -> Result<()> {
+    // prints a random input instead of solving one
+    if let Some((seed, size)) = common::random::generate_flag()? {
+        println!("{}", generate(seed, size));
+        return Ok(());
+    }
+
+    // first arg is the name of the program
+    let path = match env::args().nth(1) {
+        Some(path) => path,
+        None => String::from("input.txt"),
+    };
+    // rest of the args are optional flags
+    let flags: Vec<String> = env::args().skip(2).collect();
+    let input = common::read_input(&path)?;
+
+    let is_rpsls = flags.iter().any(|f| f == "--rpsls");
+    let mut game = if is_rpsls {
+        Game::rpsls()
+    } else {
+        Game::classic()
+    };
+    // for guides written with other letters, like --keys=ABC,RPS
+    let keys = flags.iter().find_map(|f| f.strip_prefix("--keys="));
+    if let Some(keys) = keys {
+        game = game.with_keys(keys)?;
+    }
+
+    // the guide only needs the opponent's column then, so it isn't scored
+    if flags.iter().any(|f| f == "--optimize") {
+        let mut constraints = Constraints {
+            max_losses: None,
+            no_repeat: false,
+        };
+        for flag in &flags {
+            if let Some(k) = flag.strip_prefix("--max-losses=") {
+                constraints.max_losses = Some(k.parse()?);
+            } else if flag == "--no-repeat" {
+                constraints.no_repeat = true;
+            }
+        }
+        print!("{}", optimize_guide(&game, &input, &constraints)?);
+        return Ok(());
+    }
+
+    if is_rpsls || keys.is_some() {
+        println!("{}", score_guide(&game, &input, Game::parse_round_part1)?);
+        println!("{}", score_guide(&game, &input, Game::parse_round_part2)?);
+    } else {
+        println!("{}", part1(&input)?);
+        println!("{}", part2(&input)?);
+    }
+
+    if flags.iter().any(|f| f == "--report") {
+        for (name, parse_round) in [
+            (
+                "part 1",
+                Game::parse_round_part1 as fn(&Game, &str) -> Result<(Shape, Shape)>,
+            ),
+            ("part 2", Game::parse_round_part2),
+        ] {
+            let rounds: Result<Vec<_>> = input.lines().map(|l| parse_round(&game, l)).collect();
+            println!("{name}:");
+            print!("{}", report(&game, &rounds?));
+        }
+    }
+
+    Ok(())
+}
+
+// both parts, for aoc run
+pub struct Solution;
+
+impl common::Day for Solution {
+    fn part1(input: &str) -> Result<String> {
+        part1(input)
+    }
+
+    fn part2(input: &str) -> Result<String> {
+        part2(input)
+    }
+}
+
+fn part1(input: &str) -> Result<String> {
+    score_guide(&Game::classic(), input, Game::parse_round_part1)
+}
+
+fn part2(input: &str) -> Result<String> {
+    score_guide(&Game::classic(), input, Game::parse_round_part2)
+}
+
+fn score_guide(
+    game: &Game,
+    input: &str,
+    parse_round: fn(&Game, &str) -> Result<(Shape, Shape)>,
+) -> Result<String> {
+    // rust has an implementation to convert from Vec<Result<_>> to Result<Vec<_>>
+    // we just need to provide the wanted type here
+    let rounds: Result<Vec<_>> = input.lines().map(|l| parse_round(game, l)).collect();
+    // i64 so that no input that fits in memory can overflow the total
+    let score: i64 = rounds?.iter().map(|r| i64::from(get_score(game, r))).sum();
+
+    Ok(score.to_string())
+}
+
+fn get_score(game: &Game, round: &(Shape, Shape)) -> i32 {
+    let (opponent, me) = *round;
+    outcome_score(game.outcome(opponent, me)) + shape_score(me)
+}
+
+fn outcome_score(outcome: Outcome) -> i32 {
+    match outcome {
+        Outcome::Lose => 0,
+        Outcome::Draw => 3,
+        Outcome::Win => 6,
+    }
+}
+
+fn shape_score(shape: Shape) -> i32 {
+    shape.0 as i32 + 1
+}
+
+// one line per round with both parts of the score, and totals at the end
+fn report(game: &Game, rounds: &[(Shape, Shape)]) -> String {
+    let mut lines: Vec<String> = vec![format!(
+        "{:>5}  {:<10}{:<10}{:<6}{:>8}{:>7}{:>7}",
+        "round", "opponent", "me", "result", "outcome", "shape", "score"
+    )];
+    let (mut wins, mut draws, mut losses) = (0, 0, 0);
+    let mut total: i64 = 0;
+
+    for (i, &(opponent, me)) in rounds.iter().enumerate() {
+        let outcome = game.outcome(opponent, me);
+        let result = match outcome {
+            Outcome::Lose => {
+                losses += 1;
+                "lose"
+            }
+            Outcome::Draw => {
+                draws += 1;
+                "draw"
+            }
+            Outcome::Win => {
+                wins += 1;
+                "win"
+            }
+        };
+        let score = outcome_score(outcome) + shape_score(me);
+        total += i64::from(score);
+        lines.push(format!(
+            "{:>5}  {:<10}{:<10}{:<6}{:>8}{:>7}{:>7}",
+            i + 1,
+            game.names[opponent.0],
+            game.names[me.0],
+            result,
+            outcome_score(outcome),
+            shape_score(me),
+            score
+        ));
+    }
+
+    lines.push(format!(
+        "wins: {wins}, draws: {draws}, losses: {losses}, total score: {total}"
+    ));
+    lines.join("\n") + "\n"
+}
+
+struct Constraints {
+    max_losses: Option<usize>,
+    // never play the same shape twice in a row
+    no_repeat: bool,
+}
+
+#[derive(Clone, Copy)]
+enum Goal {
+    Best,
+    Worst,
+}
+
+// Best and worst total for --optimize, each followed by my keys to play, like
+// "best: 24 YZX".
+fn optimize_guide(game: &Game, input: &str, constraints: &Constraints) -> Result<String> {
+    let opponents: Result<Vec<_>> = input.lines().map(|l| game.parse_opponent(l)).collect();
+    let opponents = opponents?;
+    let mut result = String::new();
+    for (name, goal) in [("best", Goal::Best), ("worst", Goal::Worst)] {
+        let line = match optimize(game, &opponents, constraints, goal) {
+            Some((score, shapes)) => {
+                let keys: String = shapes.iter().map(|s| game.my_keys[s.0]).collect();
+                format!("{name}: {score} {keys}")
+            }
+            None => format!("{name}: impossible"),
+        };
+        result += line.trim_end();
+        result += "\n";
+    }
+    Ok(result)
+}
+
+// Picks my shape for every round when only the opponent's shapes are known.
+// Dynamic programming over the rounds, where the state is how many rounds
+// have been lost so far and which shape was played last. Returns the total
+// score and the picked shapes, or None if the constraints can't be met.
+fn optimize(
+    game: &Game,
+    opponents: &[Shape],
+    constraints: &Constraints,
+    goal: Goal,
+) -> Option<(i64, Vec<Shape>)> {
+    // nothing to pick, and nothing to lose either
+    if opponents.is_empty() {
+        return Some((0, Vec::new()));
+    }
+    let n = game.names.len();
+    // Losses don't need to be tracked at all without a limit, and neither with
+    // a limit of at least one loss per round, since that can never be hit.
+    let limit = constraints.max_losses.filter(|k| *k < opponents.len());
+    let max_losses = limit.unwrap_or(0);
+    let is_lost = |opponent: Shape, me: usize| {
+        limit.is_some() && game.outcome(opponent, Shape(me)) == Outcome::Lose
+    };
+    let is_better = |a: i64, b: i64| match goal {
+        Goal::Best => a > b,
+        Goal::Worst => a < b,
+    };
+
+    // scores[losses * n + last shape], None when the state can't be reached
+    let state_count = (max_losses + 1) * n;
+    // i64 for the same reason as in score_guide
+    let mut scores: Vec<Option<i64>> = vec![None; state_count];
+    // For each round and state, the shape played the round before on the best
+    // path there. The losses before follow from whether this round was lost.
+    // u32 is plenty, a game with more shapes wouldn't fit in memory.
+    let mut parents: Vec<Vec<u32>> = Vec::with_capacity(opponents.len());
+
+    for (round, &opponent) in opponents.iter().enumerate() {
+        // (losses, last shape, score) of every state reached so far
+        let previous_states: Vec<(usize, usize, i64)> = scores
+            .iter()
+            .enumerate()
+            .filter_map(|(i, score)| score.map(|score| (i / n, i % n, score)))
+            .collect();
+        let mut next_scores: Vec<Option<i64>> = vec![None; state_count];
+        let mut next_parents: Vec<u32> = vec![0; state_count];
+
+        for me in 0..n {
+            let round_score = i64::from(get_score(game, &(opponent, Shape(me))));
+            // first round doesn't have a previous shape
+            let first_state = [(0, me, 0)];
+            let states = if round == 0 {
+                &first_state[..]
+            } else {
+                &previous_states[..]
+            };
+
+            for &(losses, last, score) in states {
+                if round > 0 && constraints.no_repeat && last == me {
+                    continue;
+                }
+                let losses_after = losses + is_lost(opponent, me) as usize;
+                if losses_after > max_losses {
+                    continue;
+                }
+                let total = score + round_score;
+                let state = losses_after * n + me;
+                if next_scores[state].is_none_or(|current| is_better(total, current)) {
+                    next_scores[state] = Some(total);
+                    next_parents[state] = last as u32;
+                }
+            }
+        }
+
+        scores = next_scores;
+        parents.push(next_parents);
+    }
+
+    // pick the best end state and walk back the parents
+    let mut best: Option<(i64, usize)> = None;
+    for (state, score) in scores.iter().enumerate() {
+        if let Some(score) = *score {
+            if best.is_none_or(|(current, _)| is_better(score, current)) {
+                best = Some((score, state));
+            }
+        }
+    }
+    let (total, state) = best?;
+    let (mut losses, mut last) = (state / n, state % n);
+    let mut shapes: Vec<Shape> = Vec::with_capacity(opponents.len());
+    for (round_parents, &opponent) in parents.iter().zip(opponents).rev() {
+        shapes.push(Shape(last));
+        let previous = round_parents[losses * n + last] as usize;
+        losses -= is_lost(opponent, last) as usize;
+        last = previous;
+    }
+    shapes.reverse();
+
+    Some((total, shapes))
+}
+
+// index into Game::names
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Shape(usize);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+// Shapes are listed in a cycle where each shape beats the (n - 1) / 2 shapes
+// before it and loses to the (n - 1) / 2 shapes after it. With Rock, Paper,
+// Scissors that is the usual game, and the same rule works for any odd amount
+// of shapes, like Rock, Spock, Paper, Lizard, Scissors.
+struct Game {
+    names: Vec<String>,
+    opponent_keys: Vec<char>,
+    my_keys: Vec<char>,
+    // lose, draw and win for part 2
+    outcome_keys: [char; 3],
+}
+
+impl Game {
+    fn new(names: &[&str], opponent_keys: &str, my_keys: &str, outcome_keys: &str) -> Result<Game> {
+        if names.len() < 3 || names.len().is_multiple_of(2) {
+            return Err("game needs an odd amount of shapes, at least 3".into());
+        }
+        let opponent_keys: Vec<char> = opponent_keys.chars().collect();
+        let my_keys: Vec<char> = my_keys.chars().collect();
+        if opponent_keys.len() != names.len() || my_keys.len() != names.len() {
+            return Err("every shape needs exactly one key".into());
+        }
+        let outcome_keys: [char; 3] = outcome_keys
+            .chars()
+            .collect::<Vec<_>>()
+            .try_into()
+            .map_err(|_| "lose, draw and win need exactly one key each")?;
+        for keys in [&opponent_keys[..], &my_keys, &outcome_keys] {
+            if keys.iter().enumerate().any(|(i, k)| keys[..i].contains(k)) {
+                let keys: String = keys.iter().collect();
+                return Err(format!("same key used twice in {keys:?}").into());
+            }
+        }
+        Ok(Game {
+            names: names.iter().map(|s| s.to_string()).collect(),
+            opponent_keys,
+            my_keys,
+            outcome_keys,
+        })
+    }
+
+    fn classic() -> Game {
+        Game::new(&["Rock", "Paper", "Scissors"], "ABC", "XYZ", "XYZ").unwrap()
+    }
+
+    fn rpsls() -> Game {
+        Game::new(
+            &["Rock", "Spock", "Paper", "Lizard", "Scissors"],
+            "ABCDE",
+            "VWXYZ",
+            "XYZ",
+        )
+        .unwrap()
+    }
+
+    // Same shapes with other keys, from "OPPONENT,ME" or
+    // "OPPONENT,ME,OUTCOME" like "ABC,XYZ,LDW".
+    fn with_keys(&self, src: &str) -> Result<Game> {
+        let names: Vec<&str> = self.names.iter().map(String::as_str).collect();
+        let outcome_keys: String = self.outcome_keys.iter().collect();
+        match src.split(',').collect::<Vec<_>>()[..] {
+            [opponent, me] => Game::new(&names, opponent, me, &outcome_keys),
+            [opponent, me, outcome] => Game::new(&names, opponent, me, outcome),
+            _ => Err(format!("expected keys like \"ABC,XYZ\", got {src:?}").into()),
+        }
+    }
+
+    fn outcome(&self, opponent: Shape, me: Shape) -> Outcome {
+        let n = self.names.len();
+        // how many steps forward in the cycle from opponent to me
+        let distance = (me.0 + n - opponent.0) % n;
+        if distance == 0 {
+            Outcome::Draw
+        } else if distance <= (n - 1) / 2 {
+            Outcome::Win
+        } else {
+            Outcome::Lose
+        }
+    }
+
+    // with more than 3 shapes there are many choices, pick the nearest one
+    fn shape_for_outcome(&self, opponent: Shape, outcome: Outcome) -> Shape {
+        let n = self.names.len();
+        match outcome {
+            Outcome::Lose => Shape((opponent.0 + n - 1) % n),
+            Outcome::Draw => opponent,
+            Outcome::Win => Shape((opponent.0 + 1) % n),
+        }
+    }
+
+    fn parse_opponent(&self, src: &str) -> Result<Shape> {
+        let key = src.chars().next().ok_or("unexpected input")?;
+        let index = self
+            .opponent_keys
+            .iter()
+            .position(|k| *k == key)
+            .ok_or("invalid opponent shape")?;
+        Ok(Shape(index))
+    }
+
+    fn parse_round_part1(&self, src: &str) -> Result<(Shape, Shape)> {
+        let opponent = self.parse_opponent(src)?;
+        let key = src.chars().nth(2).ok_or("unexpected input")?;
+        let index = self
+            .my_keys
+            .iter()
+            .position(|k| *k == key)
+            .ok_or("invalid my shape")?;
+
+        Ok((opponent, Shape(index)))
+    }
+
+    fn parse_round_part2(&self, src: &str) -> Result<(Shape, Shape)> {
+        let opponent = self.parse_opponent(src)?;
+        let outcome = match src.chars().nth(2).ok_or("unexpected input")? {
+            c if c == self.outcome_keys[0] => Ok(Outcome::Lose),
+            c if c == self.outcome_keys[1] => Ok(Outcome::Draw),
+            c if c == self.outcome_keys[2] => Ok(Outcome::Win),
+            _ => Err("invalid round result"),
+        }?;
+
+        Ok((opponent, self.shape_for_outcome(opponent, outcome)))
+    }
+}
+
+// size is the amount of rounds
+fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let rounds: Vec<String> = (0..size)
+        .map(|_| {
+            format!(
+                "{} {}",
+                rng.choose(&['A', 'B', 'C']),
+                rng.choose(&['X', 'Y', 'Z'])
+            )
+        })
+        .collect();
+    rounds.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("../examples/example.txt");
+
+    // example_* tests, generated by build.rs from examples/manifest
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+
+    #[test]
+    fn rpsls_test() -> Result<()> {
+        let game = Game::rpsls();
+        let [rock, spock, paper, lizard, scissors] = [0, 1, 2, 3, 4].map(Shape);
+        assert_eq!(game.outcome(rock, lizard), Outcome::Lose);
+        assert_eq!(game.outcome(rock, scissors), Outcome::Lose);
+        assert_eq!(game.outcome(rock, paper), Outcome::Win);
+        assert_eq!(game.outcome(rock, spock), Outcome::Win);
+        assert_eq!(game.outcome(lizard, spock), Outcome::Lose);
+        assert_eq!(game.outcome(lizard, paper), Outcome::Lose);
+        assert_eq!(game.outcome(lizard, lizard), Outcome::Draw);
+
+        // A X = Rock vs Paper (3 + 6), E V = Scissors vs Rock (1 + 6)
+        let actual = score_guide(&game, "A X\nE V", Game::parse_round_part1)?;
+        assert_eq!(actual, "16");
+
+        Ok(())
+    }
+
+    #[test]
+    fn optimize_test() {
+        let game = Game::classic();
+        let free = Constraints {
+            max_losses: None,
+            no_repeat: false,
+        };
+        let [rock, paper, scissors] = [0, 1, 2].map(Shape);
+
+        let opponents = [rock, paper, scissors];
+        let best = optimize(&game, &opponents, &free, Goal::Best);
+        assert_eq!(best, Some((24, vec![paper, scissors, rock])));
+        let worst = optimize(&game, &opponents, &free, Goal::Worst);
+        assert_eq!(worst, Some((6, vec![scissors, rock, paper])));
+
+        let opponents = [rock, rock, rock];
+        let no_repeat = Constraints {
+            max_losses: Some(1),
+            no_repeat: true,
+        };
+        let best = optimize(&game, &opponents, &no_repeat, Goal::Best);
+        assert_eq!(best, Some((20, vec![paper, rock, paper])));
+
+        let never_lose = Constraints {
+            max_losses: Some(0),
+            no_repeat: false,
+        };
+        let worst = optimize(&game, &opponents, &never_lose, Goal::Worst);
+        assert_eq!(worst, Some((12, vec![rock, rock, rock])));
+
+        // an empty guide can always be followed
+        assert_eq!(
+            optimize(&game, &[], &never_lose, Goal::Best),
+            Some((0, vec![]))
+        );
+    }
+
+    #[test]
+    fn optimize_guide_test() -> Result<()> {
+        let constraints = Constraints {
+            max_losses: None,
+            no_repeat: false,
+        };
+        // only the opponent's column, which part 1 and 2 can't score
+        let input = "A\nB\nC";
+        assert!(part1(input).is_err());
+        let expected = "best: 24 YZX\nworst: 6 ZXY\n";
+        assert_eq!(
+            optimize_guide(&Game::classic(), input, &constraints)?,
+            expected
+        );
+        let expected = "best: 0\nworst: 0\n";
+        assert_eq!(
+            optimize_guide(&Game::classic(), "", &constraints)?,
+            expected
+        );
+        assert!(optimize_guide(&Game::classic(), "A\nX", &constraints).is_err());
+
+        // more shapes than fit in a byte
+        let names: Vec<String> = (0..301).map(|i| format!("shape {i}")).collect();
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        let keys: String = (0..301).filter_map(|i| char::from_u32(0x100 + i)).collect();
+        let game = Game::new(&names, &keys, &keys, "XYZ")?;
+        let opponents = [Shape(300), Shape(0)];
+        let (total, shapes) =
+            optimize(&game, &opponents, &constraints, Goal::Best).ok_or("impossible")?;
+        // a high shape is worth more than winning: draw 301 + 3, then lose 301 + 0
+        assert_eq!(total, 605);
+        assert_eq!(shapes, vec![Shape(300), Shape(300)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn report_test() -> Result<()> {
+        let game = Game::classic();
+        let rounds: Result<Vec<_>> = INPUT.lines().map(|l| game.parse_round_part1(l)).collect();
+        let expected = concat!(
+            "round  opponent  me        result outcome  shape  score\n",
+            "    1  Rock      Paper     win          6      2      8\n",
+            "    2  Paper     Rock      lose         0      1      1\n",
+            "    3  Scissors  Scissors  draw         3      3      6\n",
+            "wins: 1, draws: 1, losses: 1, total score: 15\n",
+        );
+        assert_eq!(report(&game, &rounds?), expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_game_test() {
+        let names = ["Rock", "Paper", "Scissors"];
+        assert!(Game::new(&["Rock", "Paper"], "AB", "XY", "XYZ").is_err());
+        assert!(Game::new(&names, "AB", "XYZ", "XYZ").is_err());
+        assert!(Game::new(&names, "ABC", "XYZ", "XY").is_err());
+        assert!(Game::new(&names, "ABC", "XYZ", "WXYZ").is_err());
+        assert!(Game::new(&names, "ABA", "XYZ", "XYZ").is_err());
+        assert!(Game::new(&names, "ABC", "XXZ", "XYZ").is_err());
+        assert!(Game::new(&names, "ABC", "XYZ", "XYX").is_err());
+        // the columns are apart, so they can share keys
+        assert!(Game::new(&names, "ABC", "ABC", "ABC").is_ok());
+    }
+
+    #[test]
+    fn with_keys_test() -> Result<()> {
+        let game = Game::classic().with_keys("RPS,rps")?;
+        assert_eq!(
+            score_guide(&game, "R p\nP r\nS s", Game::parse_round_part1)?,
+            "15"
+        );
+        // outcome keys stay the same unless given
+        assert_eq!(
+            score_guide(&game, "R X\nP Z", Game::parse_round_part2)?,
+            "12"
+        );
+        let game = Game::classic().with_keys("RPS,rps,ldw")?;
+        assert_eq!(
+            score_guide(&game, "R d\nP l\nS w", Game::parse_round_part2)?,
+            "12"
+        );
+
+        let game = Game::rpsls().with_keys("12345,abcde")?;
+        assert_eq!(game.names.len(), 5);
+        for keys in [
+            "ABC",
+            "ABC,XYZ,LDW,X",
+            "ABCD,XYZ",
+            "ABC,XYZ,LD",
+            "AAC,XYZ",
+            "ABC,XYZ,LLW",
+        ] {
+            assert!(Game::classic().with_keys(keys).is_err(), "{keys}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn generate_test() -> Result<()> {
+        let input = generate(1, 100);
+        assert_eq!(input, generate(1, 100));
+        assert_ne!(input, generate(2, 100));
+        part1(&input)?;
+        part2(&input)?;
+
+        Ok(())
+    }
+
+    // every possible sequence of my shapes, scored if it meets the constraints
+    fn brute_force_scores(game: &Game, opponents: &[Shape], constraints: &Constraints) -> Vec<i64> {
+        let n = game.names.len();
+        let mut scores = Vec::new();
+        for code in 0..n.pow(opponents.len() as u32) {
+            let shapes: Vec<Shape> = (0..opponents.len())
+                .map(|round| Shape(code / n.pow(round as u32) % n))
+                .collect();
+            if meets(game, opponents, &shapes, constraints) {
+                scores.push(total_score(game, opponents, &shapes));
+            }
+        }
+        scores
+    }
+
+    fn total_score(game: &Game, opponents: &[Shape], shapes: &[Shape]) -> i64 {
+        let rounds = opponents.iter().zip(shapes);
+        rounds
+            .map(|(o, m)| i64::from(get_score(game, &(*o, *m))))
+            .sum()
+    }
+
+    fn meets(
+        game: &Game,
+        opponents: &[Shape],
+        shapes: &[Shape],
+        constraints: &Constraints,
+    ) -> bool {
+        let losses = opponents
+            .iter()
+            .zip(shapes)
+            .filter(|(o, m)| game.outcome(**o, **m) == Outcome::Lose)
+            .count();
+        let repeats = shapes.windows(2).any(|w| w[0] == w[1]);
+        constraints.max_losses.is_none_or(|k| losses <= k) && !(constraints.no_repeat && repeats)
+    }
+
+    #[test]
+    fn optimize_brute_force_test() {
+        let mut rng = Rng::new(0);
+        for game in [Game::classic(), Game::rpsls()] {
+            for _ in 0..20 {
+                let round_count = rng.range(1..=5) as usize;
+                let n = game.names.len() as u64;
+                let opponents: Vec<Shape> = (0..round_count)
+                    .map(|_| Shape(rng.below(n) as usize))
+                    .collect();
+                let constraints = Constraints {
+                    max_losses: rng.percent(50).then(|| rng.below(3) as usize),
+                    no_repeat: rng.percent(50),
+                };
+                let scores = brute_force_scores(&game, &opponents, &constraints);
+                for (goal, expected) in [
+                    (Goal::Best, scores.iter().max()),
+                    (Goal::Worst, scores.iter().min()),
+                ] {
+                    let result = optimize(&game, &opponents, &constraints, goal);
+                    assert_eq!(result.as_ref().map(|(total, _)| total), expected);
+                    if let Some((total, shapes)) = result {
+                        assert!(meets(&game, &opponents, &shapes, &constraints));
+                        assert_eq!(total_score(&game, &opponents, &shapes), total);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fuzz_parse_round_part1_test() {
+        common::fuzz::fuzz(&[INPUT, &generate(0, 10)], |input| {
+            let _ = part1(input);
+            let _ = score_guide(&Game::rpsls(), input, Game::parse_round_part1);
+        });
+    }
+
+    #[test]
+    fn fuzz_parse_round_part2_test() {
+        common::fuzz::fuzz(&[INPUT, &generate(0, 10)], |input| {
+            let _ = part2(input);
+            let game = Game::rpsls();
+            if let Ok(rounds) = input
+                .lines()
+                .map(|l| game.parse_round_part2(l))
+                .collect::<Result<Vec<_>>>()
+            {
+                report(&game, &rounds);
+            }
+        });
+    }
+
+    #[test]
+    fn fuzz_parse_opponent_test() {
+        common::fuzz::fuzz(&[INPUT, &generate(0, 10)], |input| {
+            let game = Game::classic();
+            if let Ok(opponents) = input
+                .lines()
+                .map(|l| game.parse_opponent(l))
+                .collect::<Result<Vec<_>>>()
+            {
+                let constraints = Constraints {
+                    max_losses: Some(1),
+                    no_repeat: true,
+                };
+                optimize(&game, &opponents, &constraints, Goal::Best);
+            }
+        });
+    }
+}
@@ -0,0 +1,230 @@
+use common::{Result, Rng};
+use std::collections::HashSet;
+use std::env;
+
+// everything the binary does, main.rs only calls this
+pub fn run() -> Result<()> {
+    // prints a random input instead of solving one
+    if let Some((seed, size)) = common::random::generate_flag()? {
+        println!("{}", generate(seed, size));
+        return Ok(());
+    }
+
+    // first arg is the name of the program
+    let path = match env::args().nth(1) {
+        Some(path) => path,
+        None => String::from("input.txt"),
+    };
+    // rest of the args are optional flags
+    let flags: Vec<String> = env::args().skip(2).collect();
+    let input = common::read_input(&path)?;
+
+    if flags.iter().any(|f| f == "--naive") {
+        println!("{}", part1_naive(&input)?);
+        println!("{}", part2_naive(&input)?);
+        return Ok(());
+    }
+
+    println!("{}", part1(&input)?);
+    println!("{}", part2(&input)?);
+    Ok(())
+}
+
+// both parts, for aoc run
+pub struct Solution;
+
+impl common::Day for Solution {
+    fn part1(input: &str) -> Result<String> {
+        part1(input)
+    }
+
+    fn part2(input: &str) -> Result<String> {
+        part2(input)
+    }
+}
+
+fn part1(input: &str) -> Result<String> {
+    let mut priority_sum = 0;
+    for line in input.lines() {
+        let items: Vec<char> = line.chars().collect();
+        let (compartment1, compartment2) = items.split_at(items.len() / 2);
+        priority_sum += shared_priority(item_mask(compartment1)? & item_mask(compartment2)?)?;
+    }
+    Ok(priority_sum.to_string())
+}
+
+fn part2(input: &str) -> Result<String> {
+    let sacks: Vec<u64> = input
+        .lines()
+        .map(|line| item_mask(&line.chars().collect::<Vec<_>>()))
+        .collect::<Result<_>>()?;
+
+    let mut priority_sum = 0;
+    for group in sacks.chunks(3) {
+        if group.len() < 3 {
+            return Err("last group has less than three elves".into());
+        }
+        priority_sum += shared_priority(group[0] & group[1] & group[2])?;
+    }
+    Ok(priority_sum.to_string())
+}
+
+// Bit n is set if there is an item with priority n. Sets of item types as
+// bits are a lot cheaper to intersect than HashSets.
+fn item_mask(items: &[char]) -> Result<u64> {
+    let mut mask = 0;
+    for c in items {
+        mask |= 1 << priority(*c).ok_or_else(|| format!("invalid item type {c:?}"))?;
+    }
+    Ok(mask)
+}
+
+fn shared_priority(shared: u64) -> Result<u32> {
+    match shared.count_ones() {
+        1 => Ok(shared.trailing_zeros()),
+        count => Err(format!("expected one shared item type, found {count}").into()),
+    }
+}
+
+fn priority(c: char) -> Option<u32> {
+    match c {
+        'a'..='z' => Some(c as u32 - 'a' as u32 + 1),
+        'A'..='Z' => Some(c as u32 - 'A' as u32 + 27),
+        _ => None,
+    }
+}
+
+// the first version with HashSets, kept around to check the one above
+fn part1_naive(input: &str) -> Result<String> {
+    let mut priority_sum = 0;
+    for line in input.lines() {
+        let count_half = line.chars().count() / 2;
+        let compartment1: HashSet<_> = line.chars().take(count_half).collect();
+        let compartment2: HashSet<_> = line.chars().skip(count_half).collect();
+        let mut intersection = compartment1.intersection(&compartment2);
+        let common_item = intersection.next().ok_or("no shared item type")?;
+        let priority = calculate_priority(*common_item)?;
+        priority_sum += priority;
+    }
+    Ok(priority_sum.to_string())
+}
+
+fn part2_naive(input: &str) -> Result<String> {
+    let sacks: Vec<_> = input
+        .lines()
+        .map(|s| s.chars().collect::<HashSet<_>>())
+        .collect();
+
+    let mut priority_sum = 0;
+    for group in sacks.chunks(3) {
+        let [sack1, sack2, sack3] = group else {
+            return Err("last group has less than three elves".into());
+        };
+
+        let intersection12: HashSet<_> = sack1.intersection(sack2).cloned().collect();
+        let mut intersection123 = intersection12.intersection(sack3);
+        let common_item = intersection123.next().ok_or("no badge item type")?;
+        let priority = calculate_priority(*common_item)?;
+        priority_sum += priority;
+    }
+    Ok(priority_sum.to_string())
+}
+
+fn calculate_priority(c: char) -> Result<u32> {
+    let ascii = c as u32;
+    match c {
+        'a'..='z' => Ok(ascii - 96),
+        'A'..='Z' => Ok(ascii - 64 + 26),
+        _ => Err(format!("invalid item type {c:?}").into()),
+    }
+}
+
+// Size is the amount of rucksacks, rounded up to whole groups of three. Each
+// rucksack has exactly one item type in both compartments and each group has
+// exactly one item type in all three rucksacks.
+fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut sacks: Vec<String> = Vec::new();
+
+    for _ in 0..size.div_ceil(3) {
+        let mut types: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+        rng.shuffle(&mut types);
+        let badge = types[0];
+        // the remaining 51 types are split between the sacks, so no other
+        // type ends up in more than one of them
+        for own in types[1..].chunks(17) {
+            let (shared, pools) = own.split_at(1);
+            let (left_pool, right_pool) = pools.split_at(8);
+            let len = rng.range(2..=12) as usize;
+            let mut compartments = [left_pool, right_pool].map(|pool| {
+                let mut items = vec![shared[0]];
+                items.extend((1..len).map(|_| *rng.choose(pool)));
+                items
+            });
+            // the badge goes into one compartment only
+            compartments[rng.below(2) as usize][1] = badge;
+            for items in compartments.iter_mut() {
+                rng.shuffle(items);
+            }
+            sacks.push(compartments.concat().into_iter().collect());
+        }
+    }
+
+    sacks.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("../examples/example.txt");
+
+    // example_* tests, generated by build.rs from examples/manifest
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+
+    #[test]
+    fn generate_test() -> Result<()> {
+        let input = generate(1, 99);
+        assert_eq!(input, generate(1, 99));
+        assert_ne!(input, generate(2, 99));
+        part1(&input)?;
+        part2(&input)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn naive_test() -> Result<()> {
+        for seed in 0..50 {
+            let input = generate(seed, seed as usize * 3);
+            assert_eq!(part1(&input)?, part1_naive(&input)?);
+            assert_eq!(part2(&input)?, part2_naive(&input)?);
+        }
+        assert_eq!(part1_naive(INPUT)?, "157");
+        assert_eq!(part2_naive(INPUT)?, "70");
+        // nothing shared, a shared item that isn't a letter, a short group
+        assert!(part1_naive("abcd").is_err());
+        assert!(part1_naive("1a1b").is_err());
+        assert!(part2_naive("vJrwpWtwJgWrhcsFMMfFFhFp").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn fuzz_part1_test() {
+        common::fuzz::fuzz(&[INPUT, &generate(0, 9)], |input| {
+            if let (Ok(fast), Ok(naive)) = (part1(input), part1_naive(input)) {
+                assert_eq!(fast, naive);
+            }
+        });
+    }
+
+    #[test]
+    fn fuzz_part2_test() {
+        common::fuzz::fuzz(&[INPUT, &generate(0, 9)], |input| {
+            if let (Ok(fast), Ok(naive)) = (part2(input), part2_naive(input)) {
+                assert_eq!(fast, naive);
+            }
+        });
+    }
+}
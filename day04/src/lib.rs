@@ -0,0 +1,442 @@
+use common::{Result, Rng};
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+
+// everything the binary does, main.rs only calls this
+pub fn run() -> Result<()> {
+    // prints a random input instead of solving one
+    if let Some((seed, size)) = common::random::generate_flag()? {
+        println!("{}", generate(seed, size));
+        return Ok(());
+    }
+
+    // first arg is the name of the program
+    let path = match env::args().nth(1) {
+        Some(path) => path,
+        None => String::from("input.txt"),
+    };
+    let input = common::read_input(&path)?;
+
+    println!("{}", part1(&input)?);
+    println!("{}", part2(&input)?);
+
+    // optional second arg: report sections covered by more than K elves
+    if let Some(k) = env::args().nth(2) {
+        let k: usize = k.parse()?;
+        let assignments: Vec<Interval> = parse_ranges(&input)?.into_iter().flatten().collect();
+        println!("uncovered: {}", format_intervals(&uncovered(&assignments)));
+        println!(
+            "covered by more than {k}: {}",
+            format_intervals(&covered_more_than(&assignments, k))
+        );
+    }
+    Ok(())
+}
+
+// both parts, for aoc run
+pub struct Solution;
+
+impl common::Day for Solution {
+    fn part1(input: &str) -> Result<String> {
+        part1(input)
+    }
+
+    fn part2(input: &str) -> Result<String> {
+        part2(input)
+    }
+}
+
+fn part1(input: &str) -> Result<String> {
+    let ranges = parse_ranges(input)?;
+    let mut count = 0;
+
+    // with more than two elves per line, any pair where one contains the other counts
+    for elves in ranges {
+        let is_contained = pairs(&elves).any(|(l, r)| l.contains(r) || r.contains(l));
+        if is_contained {
+            count += 1;
+        }
+    }
+
+    Ok(count.to_string())
+}
+
+fn part2(input: &str) -> Result<String> {
+    let ranges = parse_ranges(input)?;
+    let mut count = 0;
+
+    for elves in ranges {
+        if pairs(&elves).any(|(l, r)| l.overlaps(r)) {
+            count += 1;
+        }
+    }
+
+    Ok(count.to_string())
+}
+
+// inclusive range of section IDs
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Interval {
+    start: u32,
+    end: u32,
+}
+
+impl Interval {
+    fn new(start: u32, end: u32) -> Interval {
+        Interval { start, end }
+    }
+
+    // 0-4294967295 has one section more than fits in u32
+    fn len(&self) -> u64 {
+        u64::from(self.end - self.start) + 1
+    }
+
+    fn contains(&self, other: &Interval) -> bool {
+        self.intersection(other) == Some(*other)
+    }
+
+    fn overlaps(&self, other: &Interval) -> bool {
+        (self.start <= other.end) && (self.end >= other.start)
+    }
+
+    fn intersection(&self, other: &Interval) -> Option<Interval> {
+        if self.overlaps(other) {
+            Some(Interval::new(
+                self.start.max(other.start),
+                self.end.min(other.end),
+            ))
+        } else {
+            None
+        }
+    }
+
+    // only defined when the result is a single interval, so the two have to
+    // overlap or at least touch each other (like 2-4 and 5-7)
+    fn union(&self, other: &Interval) -> Option<Interval> {
+        let touching =
+            self.end.saturating_add(1) >= other.start && other.end.saturating_add(1) >= self.start;
+        if touching {
+            Some(Interval::new(
+                self.start.min(other.start),
+                self.end.max(other.end),
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+// Splits the span of all intervals into pieces where the amount of elves
+// covering each section stays the same. Classic sweep line: +1 when an
+// interval starts, -1 after it ends.
+fn coverage(intervals: &[Interval]) -> Vec<(Interval, usize)> {
+    let mut events: BTreeMap<u64, i64> = BTreeMap::new();
+    for interval in intervals {
+        *events.entry(interval.start as u64).or_insert(0) += 1;
+        *events.entry(interval.end as u64 + 1).or_insert(0) -= 1;
+    }
+
+    let mut result: Vec<(Interval, usize)> = Vec::new();
+    let mut count: i64 = 0;
+    let mut previous: Option<u64> = None;
+    for (&position, &delta) in events.iter() {
+        if let Some(start) = previous {
+            // positions are at most u32::MAX + 1 so the end fits in u32
+            result.push((
+                Interval::new(start as u32, (position - 1) as u32),
+                count as usize,
+            ));
+        }
+        count += delta;
+        previous = Some(position);
+    }
+    result
+}
+
+// sections inside the span of all assignments that no elf is assigned to
+fn uncovered(intervals: &[Interval]) -> Vec<Interval> {
+    coverage(intervals)
+        .into_iter()
+        .filter(|(_, count)| *count == 0)
+        .map(|(interval, _)| interval)
+        .collect()
+}
+
+fn covered_more_than(intervals: &[Interval], k: usize) -> Vec<Interval> {
+    let mut result: Vec<Interval> = Vec::new();
+    for (interval, count) in coverage(intervals) {
+        if count <= k {
+            continue;
+        }
+        // neighbouring pieces with different counts can both be above k
+        match result.last().and_then(|last| last.union(&interval)) {
+            Some(merged) => {
+                result.pop();
+                result.push(merged);
+            }
+            None => result.push(interval),
+        }
+    }
+    result
+}
+
+fn format_intervals(intervals: &[Interval]) -> String {
+    if intervals.is_empty() {
+        return String::from("none");
+    }
+    let parts: Vec<String> = intervals
+        .iter()
+        .map(|i| format!("{}-{} ({})", i.start, i.end, i.len()))
+        .collect();
+    parts.join(", ")
+}
+
+// all unordered pairs of elves on the same line
+fn pairs(elves: &[Interval]) -> impl Iterator<Item = (&Interval, &Interval)> {
+    elves
+        .iter()
+        .enumerate()
+        .flat_map(move |(i, l)| elves[i + 1..].iter().map(move |r| (l, r)))
+}
+
+#[derive(Debug, PartialEq)]
+enum ParseError {
+    MissingRange { line: usize },
+    MissingDash { line: usize, text: String },
+    InvalidNumber { line: usize, text: String },
+    ReversedRange { line: usize, start: u32, end: u32 },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::MissingRange { line } => write!(f, "line {line}: missing range"),
+            ParseError::MissingDash { line, text } => {
+                write!(f, "line {line}: expected range like 2-4, got {text:?}")
+            }
+            ParseError::InvalidNumber { line, text } => {
+                write!(f, "line {line}: invalid section id {text:?}")
+            }
+            ParseError::ReversedRange { line, start, end } => {
+                write!(f, "line {line}: range {start}-{end} ends before it starts")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// Each line is a comma separated list of ranges, one per elf. Usually there
+// are two elves per line but any amount is accepted.
+fn parse_ranges(input: &str) -> std::result::Result<Vec<Vec<Interval>>, ParseError> {
+    let mut ranges: Vec<Vec<Interval>> = Vec::new();
+
+    for (i, line) in input.lines().enumerate() {
+        let line_number = i + 1; // for error messages
+        let elves: std::result::Result<Vec<_>, _> = line
+            .split(',')
+            .map(|src| parse_interval(src, line_number))
+            .collect();
+        ranges.push(elves?);
+    }
+
+    Ok(ranges)
+}
+
+fn parse_interval(src: &str, line: usize) -> std::result::Result<Interval, ParseError> {
+    if src.is_empty() {
+        return Err(ParseError::MissingRange { line });
+    }
+    let (start, end) = src.split_once('-').ok_or_else(|| ParseError::MissingDash {
+        line,
+        text: src.to_string(),
+    })?;
+    let start = parse_section_id(start, line)?;
+    let end = parse_section_id(end, line)?;
+    // a reversed range is most likely a typo, so don't guess what was meant
+    if start > end {
+        return Err(ParseError::ReversedRange { line, start, end });
+    }
+    Ok(Interval::new(start, end))
+}
+
+fn parse_section_id(src: &str, line: usize) -> std::result::Result<u32, ParseError> {
+    let invalid = || ParseError::InvalidNumber {
+        line,
+        text: src.to_string(),
+    };
+    // str::parse would also accept a leading '+'
+    if src.is_empty() || !src.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    src.parse().map_err(|_| invalid())
+}
+
+// size is the amount of pairs
+fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut pairs: Vec<String> = Vec::new();
+    for _ in 0..size {
+        let mut range = || {
+            let start = rng.range(1..=99);
+            format!("{start}-{}", rng.range(start..=99))
+        };
+        pairs.push(format!("{},{}", range(), range()));
+    }
+    pairs.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("../examples/example.txt");
+
+    // example_* tests, generated by build.rs from examples/manifest
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+
+    #[test]
+    fn more_elves_test() -> Result<()> {
+        let input = concat!("2-4,6-8,3-3\n", "1-2,3-4,5-6\n", "1-2,3-4,4-5");
+        assert_eq!(part1(input)?, "1");
+        assert_eq!(part2(input)?, "2");
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_error_test() {
+        assert_eq!(
+            parse_ranges("2-4,6-8\n4-2,6-8"),
+            Err(ParseError::ReversedRange {
+                line: 2,
+                start: 4,
+                end: 2
+            })
+        );
+        assert_eq!(
+            parse_ranges("2-4,"),
+            Err(ParseError::MissingRange { line: 1 })
+        );
+        assert_eq!(
+            parse_ranges("2-4,68"),
+            Err(ParseError::MissingDash {
+                line: 1,
+                text: String::from("68")
+            })
+        );
+        assert_eq!(
+            parse_ranges("2-+4,6-8"),
+            Err(ParseError::InvalidNumber {
+                line: 1,
+                text: String::from("+4")
+            })
+        );
+        assert_eq!(
+            parse_ranges("2-4,6-99999999999"),
+            Err(ParseError::InvalidNumber {
+                line: 1,
+                text: String::from("99999999999")
+            })
+        );
+    }
+
+    #[test]
+    fn interval_test() {
+        let a = Interval::new(2, 6);
+        let b = Interval::new(4, 8);
+        assert_eq!(a.len(), 5);
+        assert!(!a.contains(&b));
+        assert!(Interval::new(2, 8).contains(&Interval::new(3, 7)));
+        assert!(a.overlaps(&b));
+        assert_eq!(a.intersection(&b), Some(Interval::new(4, 6)));
+        assert_eq!(a.union(&b), Some(Interval::new(2, 8)));
+        assert_eq!(a.union(&Interval::new(7, 9)), Some(Interval::new(2, 9)));
+        assert_eq!(a.union(&Interval::new(8, 9)), None);
+        assert_eq!(a.intersection(&Interval::new(8, 9)), None);
+    }
+
+    #[test]
+    fn coverage_test() {
+        let assignments = [
+            Interval::new(2, 4),
+            Interval::new(6, 8),
+            Interval::new(3, 7),
+            Interval::new(4, 4),
+        ];
+        assert_eq!(uncovered(&assignments), vec![]);
+        assert_eq!(
+            uncovered(&[Interval::new(2, 4), Interval::new(8, 9)]),
+            vec![Interval::new(5, 7)]
+        );
+        assert_eq!(
+            covered_more_than(&assignments, 1),
+            vec![Interval::new(3, 4), Interval::new(6, 7)]
+        );
+        assert_eq!(
+            covered_more_than(&assignments, 2),
+            vec![Interval::new(4, 4)]
+        );
+    }
+
+    #[test]
+    fn generate_test() -> Result<()> {
+        let input = generate(1, 100);
+        assert_eq!(input, generate(1, 100));
+        assert_ne!(input, generate(2, 100));
+        part1(&input)?;
+        part2(&input)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn coverage_count_test() -> Result<()> {
+        for seed in 0..30 {
+            let input = generate(seed, seed as usize);
+            let assignments: Vec<Interval> = parse_ranges(&input)?.into_iter().flatten().collect();
+            // count every section on its own
+            let count = |section: u32| {
+                assignments
+                    .iter()
+                    .filter(|a| a.contains(&Interval::new(section, section)))
+                    .count()
+            };
+            let sections = |intervals: Vec<Interval>| -> Vec<u32> {
+                intervals.iter().flat_map(|i| i.start..=i.end).collect()
+            };
+            let (min, max) = match (
+                assignments.iter().map(|a| a.start).min(),
+                assignments.iter().map(|a| a.end).max(),
+            ) {
+                (Some(min), Some(max)) => (min, max),
+                _ => continue,
+            };
+            let expected: Vec<u32> = (min..=max).filter(|s| count(*s) == 0).collect();
+            assert_eq!(sections(uncovered(&assignments)), expected);
+            for k in 0..4 {
+                let expected: Vec<u32> = (min..=max).filter(|s| count(*s) > k).collect();
+                assert_eq!(sections(covered_more_than(&assignments, k)), expected);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn fuzz_parse_ranges_test() {
+        common::fuzz::fuzz(
+            &[INPUT, &generate(0, 10), "0-4294967295,1-2,3-4"],
+            |input| {
+                let _ = part1(input);
+                let _ = part2(input);
+                if let Ok(ranges) = parse_ranges(input) {
+                    let assignments: Vec<Interval> = ranges.into_iter().flatten().collect();
+                    format_intervals(&uncovered(&assignments));
+                    format_intervals(&covered_more_than(&assignments, 1));
+                }
+            },
+        );
+    }
+}
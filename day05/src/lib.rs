@@ -0,0 +1,343 @@
+use common::{Result, Rng};
+use std::env;
+
+// everything the binary does, main.rs only calls this
+pub fn run() -> Result<()> {
+    // prints a random input instead of solving one
+    if let Some((seed, size)) = common::random::generate_flag()? {
+        println!("{}", generate(seed, size));
+        return Ok(());
+    }
+
+    // first arg is the name of the program
+    let path = match env::args().nth(1) {
+        Some(path) => path,
+        None => String::from("input.txt"),
+    };
+    // rest of the args are optional flags
+    let flags: Vec<String> = env::args().skip(2).collect();
+    let input = common::read_input(&path)?;
+
+    // the first, slower solution, kept around to check the faster one
+    if flags.iter().any(|f| f == "--naive") {
+        println!(
+            "{}",
+            rearrange(&input, Crane::CrateMover9000, move_crates_naive)?
+        );
+        println!(
+            "{}",
+            rearrange(&input, Crane::CrateMover9001, move_crates_naive)?
+        );
+        return Ok(());
+    }
+
+    println!("{}", part1(&input)?);
+    println!("{}", part2(&input)?);
+    Ok(())
+}
+
+// both parts, for aoc run
+pub struct Solution;
+
+impl common::Day for Solution {
+    fn part1(input: &str) -> Result<String> {
+        part1(input)
+    }
+
+    fn part2(input: &str) -> Result<String> {
+        part2(input)
+    }
+}
+
+fn part1(input: &str) -> Result<String> {
+    rearrange(input, Crane::CrateMover9000, move_crates)
+}
+
+fn part2(input: &str) -> Result<String> {
+    rearrange(input, Crane::CrateMover9001, move_crates)
+}
+
+#[derive(Clone, Copy)]
+enum Crane {
+    // moves one crate at a time, so a bunch of crates ends up upside down
+    CrateMover9000,
+    // moves a bunch of crates at once, keeping their order
+    CrateMover9001,
+}
+
+fn rearrange(
+    input: &str,
+    crane: Crane,
+    move_crates: fn(&mut [Vec<char>], &Command, Crane) -> Result<()>,
+) -> Result<String> {
+    let mut parts = input.split("\n\n");
+    let mut stacks = parse_stacks(parts.next().ok_or("stacks not found")?)?;
+    let commands = parse_commands(parts.next().ok_or("commands not found")?)?;
+
+    for command in commands {
+        // Checked here so that both movers get the same moves. The stacks have
+        // to exist and hold enough crates even when nothing ends up moving.
+        let from = stacks.get(command.from).ok_or("invalid from stack")?;
+        if from.len() < command.amount as usize {
+            return Err("unexpected empty stack".into());
+        }
+        if command.to >= stacks.len() {
+            return Err("invalid to stack".into());
+        }
+        // taking crates off a stack and putting them back changes nothing
+        if command.from == command.to {
+            continue;
+        }
+        move_crates(&mut stacks, &command, crane)?;
+    }
+
+    get_message_from_stacks(stacks)
+}
+
+// moves all crates of the command in one go
+fn move_crates(stacks: &mut [Vec<char>], command: &Command, crane: Crane) -> Result<()> {
+    let amount = command.amount as usize;
+    let from = stacks.get_mut(command.from).ok_or("invalid from stack")?;
+    let rest = from
+        .len()
+        .checked_sub(amount)
+        .ok_or("unexpected empty stack")?;
+    if command.to >= stacks.len() {
+        return Err("invalid to stack".into());
+    }
+    let mut items = stacks[command.from].split_off(rest);
+    if let Crane::CrateMover9000 = crane {
+        items.reverse();
+    }
+    stacks[command.to].extend(items);
+    Ok(())
+}
+
+// the first version, moves the crates one by one like the crane would
+fn move_crates_naive(stacks: &mut [Vec<char>], command: &Command, crane: Crane) -> Result<()> {
+    match crane {
+        Crane::CrateMover9000 => {
+            for _ in 0..command.amount {
+                // we can't borrow 'stacks' as mutable twice in the same block... so extract item first
+                let item: char = {
+                    let from = stacks.get_mut(command.from).ok_or("invalid from stack")?;
+                    from.pop().ok_or("unexpected empty stack")?
+                };
+                let to = stacks.get_mut(command.to).ok_or("invalid to stack")?;
+                to.push(item);
+            }
+        }
+        Crane::CrateMover9001 => {
+            let mut items: Vec<char> = Vec::new();
+            for _ in 0..command.amount {
+                let from = stacks.get_mut(command.from).ok_or("invalid from stack")?;
+                let item = from.pop().ok_or("unexpected empty stack")?;
+                items.push(item);
+            }
+            // insert in reverse order... even if it's not a realistic crane now though :D
+            for &item in items.iter().rev() {
+                let to = stacks.get_mut(command.to).ok_or("invalid to stack")?;
+                to.push(item);
+            }
+        }
+    }
+    Ok(())
+}
+
+struct Command {
+    amount: u32,
+    from: usize,
+    to: usize,
+}
+
+fn parse_stacks(src: &str) -> Result<Vec<Vec<char>>> {
+    let mut iter = src.lines().rev();
+    let last_line = iter.next().ok_or("stack numbers not found")?;
+    let stack_count = last_line.split_whitespace().count();
+    let mut stacks: Vec<Vec<char>> = Vec::with_capacity(stack_count);
+
+    for _ in 0..stack_count {
+        stacks.push(Vec::new())
+    }
+
+    for line in iter {
+        for (i, stack) in stacks.iter_mut().enumerate() {
+            // trailing whitespace is stripped from the input, so a line ends
+            // after its last crate
+            match line.chars().nth(i * 4 + 1) {
+                Some(' ') | None => (),
+                Some(item) => stack.push(item),
+            }
+        }
+    }
+
+    Ok(stacks)
+}
+
+fn parse_commands(src: &str) -> Result<Vec<Command>> {
+    let mut commands: Vec<Command> = Vec::new();
+    for line in src.lines() {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let ["move", amount, "from", from, "to", to] = words[..] else {
+            return Err(format!("expected \"move N from A to B\", got {line:?}").into());
+        };
+        let amount: u32 = amount.parse()?;
+        let from: usize = from.parse()?;
+        let to: usize = to.parse()?;
+        // 1-index to 0-index
+        let (from, to) = match (from.checked_sub(1), to.checked_sub(1)) {
+            (Some(from), Some(to)) => (from, to),
+            _ => return Err(format!("stacks are numbered from 1: {line:?}").into()),
+        };
+        commands.push(Command { amount, from, to });
+    }
+    Ok(commands)
+}
+
+fn get_message_from_stacks(stacks: Vec<Vec<char>>) -> Result<String> {
+    let mut msg = String::new();
+    for mut stack in stacks {
+        msg.push(stack.pop().ok_or("unexpected empty stack")?);
+    }
+    Ok(msg)
+}
+
+// Size is the amount of moves. The moves never empty a stack, so every
+// stack still has a crate on top at the end.
+fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let stack_count = rng.range(3..=9) as usize;
+    // at least two crates per stack, so some stack always has a crate to spare
+    let stacks: Vec<Vec<char>> = (0..stack_count)
+        .map(|_| {
+            (0..rng.range(2..=8))
+                .map(|_| (b'A' + rng.below(26) as u8) as char)
+                .collect()
+        })
+        .collect();
+
+    let mut lines: Vec<String> = Vec::new();
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+    for level in (0..height).rev() {
+        let row: Vec<String> = stacks
+            .iter()
+            .map(|stack| match stack.get(level) {
+                Some(item) => format!("[{item}]"),
+                None => String::from("   "),
+            })
+            .collect();
+        lines.push(row.join(" "));
+    }
+    let labels: Vec<String> = (1..=stack_count).map(|i| format!(" {i} ")).collect();
+    lines.push(labels.join(" "));
+    lines.push(String::new());
+
+    let mut heights: Vec<usize> = stacks.iter().map(Vec::len).collect();
+    for _ in 0..size {
+        let sources: Vec<usize> = (0..stack_count).filter(|i| heights[*i] > 1).collect();
+        let from = *rng.choose(&sources);
+        let to = (from + 1 + rng.below(stack_count as u64 - 1) as usize) % stack_count;
+        let amount = rng.range(1..=heights[from] as i64 - 1) as usize;
+        heights[from] -= amount;
+        heights[to] += amount;
+        lines.push(format!("move {amount} from {} to {}", from + 1, to + 1));
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("../examples/example.txt");
+
+    // example_* tests, generated by build.rs from examples/manifest
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+
+    #[test]
+    fn generate_test() -> Result<()> {
+        let input = generate(1, 100);
+        assert_eq!(input, generate(1, 100));
+        assert_ne!(input, generate(2, 100));
+        part1(&input)?;
+        part2(&input)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn move_crates_test() -> Result<()> {
+        for seed in 0..100 {
+            let input = generate(seed, seed as usize + 1);
+            for crane in [Crane::CrateMover9000, Crane::CrateMover9001] {
+                assert_eq!(
+                    rearrange(&input, crane, move_crates)?,
+                    rearrange(&input, crane, move_crates_naive)?,
+                    "{input}"
+                );
+            }
+        }
+
+        // moves the generator never makes
+        let input = concat!(
+            "[A]\n",
+            "[B] [C]\n",
+            " 1   2 \n",
+            "\n",
+            "move 2 from 1 to 1"
+        );
+        for crane in [Crane::CrateMover9000, Crane::CrateMover9001] {
+            assert_eq!(rearrange(input, crane, move_crates)?, "AC");
+            assert_eq!(rearrange(input, crane, move_crates_naive)?, "AC");
+        }
+        for bad_move in [
+            "move 3 from 1 to 2",
+            "move 1 from 3 to 1",
+            "move 1 from 1 to 3",
+            "move 1 from 0 to 1",
+            "move 3 from 1 to 1",
+            "move 4294967295 from 1 to 1",
+            "move 1 from 1 to 2 please",
+            "take 1 from 1 to 2",
+        ] {
+            let input = format!("[A]\n[B] [C]\n 1   2 \n\n{bad_move}");
+            for crane in [Crane::CrateMover9000, Crane::CrateMover9001] {
+                assert!(rearrange(&input, crane, move_crates).is_err());
+                assert!(rearrange(&input, crane, move_crates_naive).is_err());
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn fuzz_parse_stacks_test() {
+        let input = common::normalize(INPUT);
+        let (stacks, _) = input.split_once("\n\n").unwrap_or_default();
+        common::fuzz::fuzz(&[stacks], |src| {
+            if let Ok(stacks) = parse_stacks(src) {
+                let _ = get_message_from_stacks(stacks);
+            }
+        });
+    }
+
+    #[test]
+    fn fuzz_parse_commands_test() {
+        let input = common::normalize(INPUT);
+        let (_, commands) = input.split_once("\n\n").unwrap_or_default();
+        common::fuzz::fuzz(&[commands], |src| {
+            let _ = parse_commands(src);
+        });
+    }
+
+    #[test]
+    fn fuzz_rearrange_test() {
+        common::fuzz::fuzz(&[INPUT, &generate(0, 10)], |input| {
+            for crane in [Crane::CrateMover9000, Crane::CrateMover9001] {
+                let fast = rearrange(input, crane, move_crates).ok();
+                assert_eq!(fast, rearrange(input, crane, move_crates_naive).ok());
+            }
+        });
+    }
+}
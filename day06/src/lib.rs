@@ -0,0 +1,153 @@
+use common::{Result, Rng};
+use std::collections::{HashMap, HashSet};
+use std::env;
+
+// everything the binary does, main.rs only calls this
+pub fn run() -> Result<()> {
+    // prints a random input instead of solving one
+    if let Some((seed, size)) = common::random::generate_flag()? {
+        println!("{}", generate(seed, size));
+        return Ok(());
+    }
+
+    // first arg is the name of the program
+    let path = match env::args().nth(1) {
+        Some(path) => path,
+        None => String::from("input.txt"),
+    };
+    // rest of the args are optional flags
+    let flags: Vec<String> = env::args().skip(2).collect();
+    let input = common::read_input(&path)?;
+
+    // the first, slower solution, kept around to check the faster one
+    if flags.iter().any(|f| f == "--naive") {
+        println!("{}", find_marker_naive(&input, 4));
+        println!("{}", find_marker_naive(&input, 14));
+        return Ok(());
+    }
+
+    println!("{}", part1(&input)?);
+    println!("{}", part2(&input)?);
+    Ok(())
+}
+
+// both parts, for aoc run
+pub struct Solution;
+
+impl common::Day for Solution {
+    fn part1(input: &str) -> Result<String> {
+        part1(input)
+    }
+
+    fn part2(input: &str) -> Result<String> {
+        part2(input)
+    }
+}
+
+fn part1(input: &str) -> Result<String> {
+    Ok(find_marker(input, 4).to_string())
+}
+
+fn part2(input: &str) -> Result<String> {
+    Ok(find_marker(input, 14).to_string())
+}
+
+// Returns after how many characters the first marker_len different characters
+// in a row have arrived, 0 if they never do. Looks at every character once,
+// remembering where it was last seen.
+fn find_marker(input: &str, marker_len: usize) -> usize {
+    let mut last_seen: HashMap<char, usize> = HashMap::new();
+    // the current run of different characters starts here
+    let mut start = 0;
+    for (i, c) in input.chars().enumerate() {
+        if let Some(previous) = last_seen.insert(c, i) {
+            start = start.max(previous + 1);
+        }
+        if i + 1 - start == marker_len {
+            return i + 1;
+        }
+    }
+    0
+}
+
+// the first version, checks every window on its own
+fn find_marker_naive(input: &str, marker_len: usize) -> usize {
+    let data: Vec<char> = input.chars().collect();
+    let mut nth_character = 0;
+    for (i, window) in data.windows(marker_len).enumerate() {
+        let hashset: HashSet<char> = HashSet::from_iter(window.iter().cloned());
+        if hashset.len() == marker_len {
+            // first time a marker appears is after the nth character arrives
+            // so with 4 size window, for example index 3 means after 4th window = after 7th char
+            nth_character = i + marker_len;
+            break;
+        }
+    }
+    nth_character
+}
+
+// Size is the length of the noise before the markers. The noise only uses a
+// few letters, then 14 different ones make sure both markers show up.
+fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut letters: Vec<char> = ('a'..='z').collect();
+    rng.shuffle(&mut letters);
+    let noise: String = (0..size).map(|_| *rng.choose(&letters[..6])).collect();
+    rng.shuffle(&mut letters);
+    noise + &letters[..14].iter().collect::<String>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("../examples/example.txt");
+
+    // example_* tests, generated by build.rs from examples/manifest
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+
+    #[test]
+    fn generate_test() -> Result<()> {
+        let input = generate(1, 100);
+        assert_eq!(input, generate(1, 100));
+        assert_ne!(input, generate(2, 100));
+        part1(&input)?;
+        part2(&input)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn find_marker_test() {
+        let mut rng = Rng::new(0);
+        for seed in 0..50 {
+            let generated = generate(seed, seed as usize * 5);
+            // few letters, so markers are rare and sometimes missing
+            let letters = ['a', 'b', 'c', 'd', 'e', 'f'];
+            let noise: String = (0..rng.below(50)).map(|_| *rng.choose(&letters)).collect();
+            for input in [&generated, &noise] {
+                for marker_len in 1..=14 {
+                    assert_eq!(
+                        find_marker(input, marker_len),
+                        find_marker_naive(input, marker_len),
+                        "{input} {marker_len}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fuzz_find_marker_test() {
+        common::fuzz::fuzz(&[INPUT, &generate(0, 20)], |input| {
+            assert_eq!(
+                part1(input).ok(),
+                Some(find_marker_naive(input, 4).to_string())
+            );
+            assert_eq!(
+                part2(input).ok(),
+                Some(find_marker_naive(input, 14).to_string())
+            );
+        });
+    }
+}
@@ -0,0 +1,316 @@
+use common::{Result, Rng};
+use std::collections::HashMap;
+use std::env;
+
+// everything the binary does, main.rs only calls this
+pub fn run() -> Result<()> {
+    // prints a random input instead of solving one
+    if let Some((seed, size)) = common::random::generate_flag()? {
+        println!("{}", generate(seed, size));
+        return Ok(());
+    }
+
+    // first arg is the name of the program
+    let path = match env::args().nth(1) {
+        Some(path) => path,
+        None => String::from("input.txt"),
+    };
+    // rest of the args are optional flags
+    let flags: Vec<String> = env::args().skip(2).collect();
+    let input = common::read_input(&path)?;
+
+    // the slower tree model, kept around to check the size stack
+    if flags.iter().any(|f| f == "--naive") {
+        let directory_sizes = get_directory_sizes_tree(&input)?;
+        println!("{}", sum_small_directories(&directory_sizes));
+        println!("{}", smallest_to_delete(directory_sizes)?);
+        return Ok(());
+    }
+
+    println!("{}", part1(&input)?);
+    println!("{}", part2(&input)?);
+    Ok(())
+}
+
+// both parts, for aoc run
+pub struct Solution;
+
+impl common::Day for Solution {
+    fn part1(input: &str) -> Result<String> {
+        part1(input)
+    }
+
+    fn part2(input: &str) -> Result<String> {
+        part2(input)
+    }
+}
+
+fn part1(input: &str) -> Result<String> {
+    Ok(sum_small_directories(&get_directory_sizes(input)?).to_string())
+}
+
+fn part2(input: &str) -> Result<String> {
+    Ok(smallest_to_delete(get_directory_sizes(input)?)?.to_string())
+}
+
+fn sum_small_directories(directory_sizes: &[u32]) -> u32 {
+    directory_sizes.iter().filter(|x| **x < 100000).sum()
+}
+
+fn smallest_to_delete(mut directory_sizes: Vec<u32>) -> Result<u32> {
+    // the root contains everything else, so it's the largest
+    let total_used = directory_sizes.iter().max().ok_or("no directories")?;
+    let total_unused = 70000000u32
+        .checked_sub(*total_used)
+        .ok_or("more space used than the disk has")?;
+    let needed = 30000000u32.saturating_sub(total_unused);
+
+    directory_sizes.sort();
+    let smallest = directory_sizes
+        .iter()
+        .find(|x| **x > needed)
+        .ok_or("no directory is large enough to delete")?;
+    Ok(*smallest)
+}
+
+fn get_directory_sizes(input: &str) -> Result<Vec<u32>> {
+    // The input seems to visit each directory depth first, and do "ls" on
+    // first visit.
+    // My idea here is to push the sum of all file sizes to a stack on "ls".
+    // After exiting a directory with "cd ..", top of the stack will contain
+    // the current directory's total sum. This sum needs also be added
+    // to the parents sum.
+    // Finally after the last command the rest of the stack needs to be
+    // unwinded the same way as "cd ..".
+
+    let mut directory_size_stack: Vec<u32> = Vec::new();
+    let mut result: Vec<u32> = Vec::new();
+
+    let pop_and_sum = |stack: &mut Vec<u32>| -> Result<u32> {
+        let sum = stack.pop().unwrap_or(0);
+        if let Some(parent) = stack.last_mut() {
+            *parent = parent.checked_add(sum).ok_or("directory too large")?;
+        }
+        Ok(sum)
+    };
+
+    for block in input.split("$ ").skip(1) {
+        let mut lines = block.lines();
+        let command = lines.next().ok_or("unexpected empty line")?;
+        let output: Vec<_> = lines.collect();
+
+        match command {
+            "ls" => {
+                let mut sum: u32 = 0;
+                for line in output {
+                    match line.split_once(' ') {
+                        Some(("dir", _)) => (),
+                        Some((size, _)) => {
+                            let size: u32 = size
+                                .parse()
+                                .map_err(|_| format!("invalid file size in {line:?}"))?;
+                            sum = sum.checked_add(size).ok_or("directory too large")?;
+                        }
+                        None => return Err(format!("unexpected line {line:?}").into()),
+                    }
+                }
+                directory_size_stack.push(sum);
+            }
+            "cd .." if output.is_empty() => {
+                let sum = pop_and_sum(&mut directory_size_stack)?;
+                result.push(sum);
+            }
+            _ if command.starts_with("cd ") && output.is_empty() => (),
+            _ => return Err(format!("unexpected command {command:?}").into()),
+        }
+    }
+
+    // unwind rest of the stack
+    for _ in 0..directory_size_stack.len() {
+        let sum = pop_and_sum(&mut directory_size_stack)?;
+        result.push(sum);
+    }
+
+    Ok(result)
+}
+
+// The straightforward way to get the same sizes as get_directory_sizes, in no
+// particular order: remember which files are in which directory, then add
+// up everything below each directory.
+fn get_directory_sizes_tree(input: &str) -> Result<Vec<u32>> {
+    // directories by their path from the root, with the sizes of the files
+    // directly inside them
+    let mut directories: HashMap<Vec<&str>, u32> = HashMap::new();
+    let mut cwd: Vec<&str> = Vec::new();
+    // only "ls" prints anything
+    let mut is_listing = false;
+
+    for line in input.lines() {
+        let words: Vec<&str> = line.split(' ').collect();
+        if words[0] == "$" {
+            is_listing = words[..] == ["$", "ls"];
+        }
+        match words[..] {
+            ["$", "cd", "/"] => cwd.clear(),
+            ["$", "cd", ".."] => {
+                cwd.pop();
+            }
+            ["$", "cd", name] => cwd.push(name),
+            ["$", "ls"] => {
+                directories.entry(cwd.clone()).or_insert(0);
+            }
+            ["dir", _] if is_listing => (),
+            [size, _] if is_listing => {
+                let total = directories.entry(cwd.clone()).or_insert(0);
+                *total = total
+                    .checked_add(size.parse()?)
+                    .ok_or("directory too large")?;
+            }
+            _ => return Err(format!("unexpected line {line:?}").into()),
+        }
+    }
+
+    directories
+        .keys()
+        .map(|path| {
+            directories
+                .iter()
+                .filter(|(other, _)| other.starts_with(path))
+                .try_fold(0u32, |sum, (_, size)| sum.checked_add(*size))
+                .ok_or_else(|| "directory too large".into())
+        })
+        .collect()
+}
+
+// Size is the amount of directories. The terminal log visits every directory
+// depth first and lists it once, and the disk is between 40000000 and
+// 70000000 full so there is always something to delete.
+fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let dir_count = size.max(1);
+    // directory 0 is the root, every other one goes into an earlier one
+    let mut children: Vec<Vec<usize>> = vec![Vec::new(); dir_count];
+    for dir in 1..dir_count {
+        children[rng.below(dir as u64) as usize].push(dir);
+    }
+    // Relative weights of the files, turned into sizes once the total is
+    // known. Spread over a few orders of magnitude so that there are small
+    // directories for part 1 too.
+    let weight = |rng: &mut Rng| (rng.range(1..=1000) as u64) << rng.below(16);
+    let mut files: Vec<Vec<u64>> = (0..dir_count)
+        .map(|_| (0..rng.below(5)).map(|_| weight(&mut rng)).collect())
+        .collect();
+    files[0].push(weight(&mut rng));
+
+    let total = rng.range(40_000_001..=70_000_000) as u64;
+    let weight_sum: u64 = files.iter().flatten().sum();
+    let mut remaining = total;
+    for size in files.iter_mut().flatten() {
+        *size = (total * *size / weight_sum).max(1);
+        remaining = remaining.saturating_sub(*size);
+    }
+    files[0][0] += remaining;
+
+    let mut lines: Vec<String> = vec![String::from("$ cd /")];
+    write_directory(0, &children, &files, &mut lines);
+    lines.join("\n")
+}
+
+fn write_directory(
+    dir: usize,
+    children: &[Vec<usize>],
+    files: &[Vec<u64>],
+    lines: &mut Vec<String>,
+) {
+    lines.push(String::from("$ ls"));
+    for child in &children[dir] {
+        lines.push(format!("dir {}", entry_name(*child)));
+    }
+    for (i, size) in files[dir].iter().enumerate() {
+        lines.push(format!("{size} {}.txt", entry_name(i)));
+    }
+    for child in &children[dir] {
+        lines.push(format!("$ cd {}", entry_name(*child)));
+        write_directory(*child, children, files, lines);
+        lines.push(String::from("$ cd .."));
+    }
+}
+
+// a, b, ..., z, ba, bb, ... so names never look like file sizes
+fn entry_name(mut index: usize) -> String {
+    let mut name: Vec<char> = Vec::new();
+    loop {
+        name.push((b'a' + (index % 26) as u8) as char);
+        index /= 26;
+        if index == 0 {
+            break;
+        }
+    }
+    name.iter().rev().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("../examples/example.txt");
+
+    // example_* tests, generated by build.rs from examples/manifest
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+
+    #[test]
+    fn generate_test() -> Result<()> {
+        let input = generate(1, 50);
+        assert_eq!(input, generate(1, 50));
+        assert_ne!(input, generate(2, 50));
+        part1(&input)?;
+        part2(&input)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn directory_sizes_test() -> Result<()> {
+        for seed in 0..50 {
+            let input = generate(seed, seed as usize);
+            let mut stack = get_directory_sizes(&input)?;
+            let mut tree = get_directory_sizes_tree(&input)?;
+            stack.sort();
+            tree.sort();
+            assert_eq!(stack, tree, "{input}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn malformed_test() {
+        for (from, to) in [
+            ("14848514 b.txt", "14848514b.txt"),
+            ("584 i", "58x4 i"),
+            ("$ cd e", "$ dc e"),
+            ("$ cd ..\n$ cd ..", "$ cd ..\n5 x\n$ cd .."),
+        ] {
+            let broken = INPUT.replace(from, to);
+            assert!(get_directory_sizes(&broken).is_err(), "{broken}");
+            assert!(get_directory_sizes_tree(&broken).is_err(), "{broken}");
+        }
+    }
+
+    #[test]
+    fn fuzz_get_directory_sizes_test() {
+        common::fuzz::fuzz(&[INPUT, &generate(0, 10)], |input| {
+            let _ = part1(input);
+            let _ = part2(input);
+        });
+    }
+
+    #[test]
+    fn fuzz_get_directory_sizes_tree_test() {
+        common::fuzz::fuzz(&[INPUT, &generate(0, 10)], |input| {
+            if let Ok(sizes) = get_directory_sizes_tree(input) {
+                let _ = smallest_to_delete(sizes);
+            }
+        });
+    }
+}
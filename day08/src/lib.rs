@@ -0,0 +1,388 @@
+use std::env;
+use std::fs::File;
+use std::io::Write;
+
+use common::grid::{DIAGONAL, ORTHOGONAL};
+use common::{Grid, Result, Rng};
+
+// everything the binary does, main.rs only calls this
+pub fn run() -> Result<()> {
+    // prints a random input instead of solving one
+    if let Some((seed, size)) = common::random::generate_flag()? {
+        println!("{}", generate(seed, size));
+        return Ok(());
+    }
+
+    // first arg is the name of the program
+    let path = match env::args().nth(1) {
+        Some(path) => path,
+        None => String::from("input.txt"),
+    };
+    // rest of the args are optional flags
+    let flags: Vec<String> = env::args().skip(2).collect();
+    let input = common::read_input(&path)?;
+
+    let with_diagonals = flags.iter().any(|f| f == "--diagonals");
+    let directions = if with_diagonals {
+        [ORTHOGONAL, DIAGONAL].concat()
+    } else {
+        ORTHOGONAL.to_vec()
+    };
+    // --verbose is the old debug print of the visibility map
+    let mut render_mode = None;
+    let mut image_path = None;
+    for flag in &flags {
+        if flag == "-v" || flag == "--verbose" {
+            render_mode = Some(RenderMode::Visibility);
+        } else if let Some(mode) = flag.strip_prefix("--render=") {
+            render_mode = Some(RenderMode::parse(mode)?);
+        } else if let Some(path) = flag.strip_prefix("--image=") {
+            image_path = Some(path.to_string());
+        }
+    }
+
+    println!("{}", part1(&input)?);
+    println!("{}", part2(&input)?);
+
+    // same puzzle, but trees can also be seen diagonally
+    if with_diagonals {
+        let grid = parse_grid(&input)?;
+        let visibility_map = get_visibility_map(&grid, &directions);
+        let scenic_scores = get_scenic_scores(&grid, &directions);
+        println!("{}", count_visible(&visibility_map));
+        println!("{}", scenic_scores.values().iter().max().unwrap_or(&0));
+    }
+
+    if let Some(mode) = render_mode {
+        let grid = parse_grid(&input)?;
+        print!("{}", render_text(&grid, mode, &directions));
+    }
+    // the image is the scenic score heatmap unless something else was asked
+    if let Some(path) = image_path {
+        let grid = parse_grid(&input)?;
+        let mode = render_mode.unwrap_or(RenderMode::Scenic);
+        write_pgm(&path, &grid, mode, &directions)?;
+    }
+    Ok(())
+}
+
+// both parts, for aoc run
+pub struct Solution;
+
+impl common::Day for Solution {
+    fn part1(input: &str) -> Result<String> {
+        part1(input)
+    }
+
+    fn part2(input: &str) -> Result<String> {
+        part2(input)
+    }
+}
+
+fn part1(input: &str) -> Result<String> {
+    let grid = parse_grid(input)?;
+    let visibility_map = get_visibility_map(&grid, &ORTHOGONAL);
+    Ok(count_visible(&visibility_map).to_string())
+}
+
+fn part2(input: &str) -> Result<String> {
+    let grid = parse_grid(input)?;
+    let scenic_scores = get_scenic_scores(&grid, &ORTHOGONAL);
+    let best = scenic_scores.values().iter().max().copied().unwrap_or(0);
+    Ok(best.to_string())
+}
+
+// Walks from (x, y) one (dx, dy) step at a time until falling off the grid.
+// Yields every position on the way, starting with (x, y) itself.
+fn scan_line<T>(
+    grid: &Grid<T>,
+    start: (usize, usize),
+    direction: (isize, isize),
+) -> impl Iterator<Item = (usize, usize)> + '_ {
+    std::iter::successors(Some(start), move |&position| {
+        grid.offset(position, direction)
+    })
+}
+
+fn get_visibility_map(grid: &Grid<u32>, directions: &[(isize, isize)]) -> Grid<bool> {
+    let mut visibility_map = grid.map(|_| false);
+
+    for &(dx, dy) in directions {
+        // looking in direction (dx, dy) from outside the grid, a line of sight
+        // starts from every tree that has no tree before it
+        for position in grid.positions() {
+            if grid.offset(position, (-dx, -dy)).is_some() {
+                continue;
+            }
+
+            let mut highest = None;
+            for p in scan_line(grid, position, (dx, dy)) {
+                let height = grid[p];
+                match highest {
+                    Some(h) if height <= h => (),
+                    _ => {
+                        highest = Some(height);
+                        visibility_map[p] = true;
+                    }
+                }
+            }
+        }
+    }
+
+    visibility_map
+}
+
+fn count_visible(visibility_map: &Grid<bool>) -> usize {
+    visibility_map.values().iter().filter(|x| **x).count()
+}
+
+fn get_scenic_scores(grid: &Grid<u32>, directions: &[(isize, isize)]) -> Grid<u64> {
+    let mut scores = grid.map(|_| 0);
+
+    for position in grid.positions() {
+        let height = grid[position];
+        let mut score: u64 = 1;
+        for &direction in directions {
+            // count trees until the view is blocked, the blocking tree included
+            let mut viewing_distance = 0;
+            for p in scan_line(grid, position, direction).skip(1) {
+                viewing_distance += 1;
+                if grid[p] >= height {
+                    break;
+                }
+            }
+            // with the diagonals a big enough forest could overflow even a u64
+            score = score.saturating_mul(viewing_distance);
+        }
+        scores[position] = score;
+    }
+
+    scores
+}
+
+fn parse_grid(input: &str) -> Result<Grid<u32>> {
+    Grid::parse_chars(input, |c| {
+        c.to_digit(10)
+            .ok_or_else(|| format!("invalid tree height {c:?}").into())
+    })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum RenderMode {
+    Visibility,
+    Heights,
+    Scenic,
+}
+
+impl RenderMode {
+    fn parse(src: &str) -> Result<RenderMode> {
+        match src {
+            "visibility" => Ok(RenderMode::Visibility),
+            "heights" => Ok(RenderMode::Heights),
+            "scenic" => Ok(RenderMode::Scenic),
+            _ => Err(format!("unknown render mode {src:?}").into()),
+        }
+    }
+}
+
+// one value per tree, larger is brighter
+fn get_render_values(
+    grid: &Grid<u32>,
+    mode: RenderMode,
+    directions: &[(isize, isize)],
+) -> Grid<u64> {
+    match mode {
+        RenderMode::Visibility => get_visibility_map(grid, directions).map(|v| u64::from(*v)),
+        RenderMode::Heights => grid.map(|h| u64::from(*h)),
+        RenderMode::Scenic => get_scenic_scores(grid, directions),
+    }
+}
+
+fn render_text(grid: &Grid<u32>, mode: RenderMode, directions: &[(isize, isize)]) -> String {
+    // darkest to brightest
+    const SHADES: &[u8] = b" .:-=+*#%@";
+
+    let values = get_render_values(grid, mode, directions);
+    let max = values.values().iter().max().copied().unwrap_or(0).max(1);
+    let mut result = String::with_capacity((grid.width() + 1) * grid.height());
+    for row in values.rows() {
+        for &value in row {
+            let c = match mode {
+                RenderMode::Visibility if value > 0 => 'X',
+                RenderMode::Visibility => '_',
+                _ => SHADES[scale(value, max, SHADES.len() as u64 - 1) as usize] as char,
+            };
+            result.push(c);
+        }
+        result.push('\n');
+    }
+    result
+}
+
+// value from 0..=max to 0..=top, without overflowing on large scores
+fn scale(value: u64, max: u64, top: u64) -> u64 {
+    (u128::from(value) * u128::from(top) / u128::from(max)) as u64
+}
+
+// Binary greyscale PGM, which most image viewers can open. One pixel per tree.
+fn write_pgm(
+    path: &str,
+    grid: &Grid<u32>,
+    mode: RenderMode,
+    directions: &[(isize, isize)],
+) -> Result<()> {
+    let values = get_render_values(grid, mode, directions);
+    let max = values.values().iter().max().copied().unwrap_or(0).max(1);
+
+    let mut data = format!("P5\n{} {}\n255\n", grid.width(), grid.height()).into_bytes();
+    data.extend(values.values().iter().map(|v| scale(*v, max, 255) as u8));
+    File::create(path)?.write_all(&data)?;
+    Ok(())
+}
+
+// size is the width and height of the forest
+fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let rows: Vec<String> = (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| (b'0' + rng.below(10) as u8) as char)
+                .collect()
+        })
+        .collect();
+    rows.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::io::Read;
+
+    const INPUT: &str = include_str!("../examples/example.txt");
+
+    // example_* tests, generated by build.rs from examples/manifest
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+
+    #[test]
+    fn render_test() -> Result<()> {
+        let grid = parse_grid(INPUT)?;
+        let expected = concat!("XXXXX\n", "XXX_X\n", "XX_XX\n", "X_X_X\n", "XXXXX\n");
+        assert_eq!(
+            render_text(&grid, RenderMode::Visibility, &ORTHOGONAL),
+            expected
+        );
+        let expected = concat!("- -#-\n", ":++.:\n", "*+--:\n", "--+=@\n", "-+-@ \n");
+        assert_eq!(
+            render_text(&grid, RenderMode::Heights, &ORTHOGONAL),
+            expected
+        );
+
+        let path = env::temp_dir().join(format!("day08_render_test_{}.pgm", std::process::id()));
+        let path = path.to_str().ok_or("invalid temp path")?;
+        write_pgm(path, &grid, RenderMode::Scenic, &ORTHOGONAL)?;
+        let mut data: Vec<u8> = Vec::new();
+        File::open(path)?.read_to_end(&mut data)?;
+        fs::remove_file(path)?;
+        assert!(data.starts_with(b"P5\n5 5\n255\n"));
+        assert_eq!(data.len(), 11 + 25);
+        // the best tree (score 8) is the brightest
+        assert_eq!(data[11 + 3 * 5 + 2], 255);
+
+        Ok(())
+    }
+
+    #[test]
+    fn diagonals_test() -> Result<()> {
+        let grid = parse_grid(INPUT)?;
+        // the 3 in the middle of the grid is hidden in every direction
+        let visibility_map = get_visibility_map(&grid, &DIAGONAL);
+        assert!(!visibility_map[(2, 2)]);
+        // the 5 at (1, 1) sees all the way to the bottom right corner,
+        // and one tree in the other diagonal directions
+        let scenic_scores = get_scenic_scores(&grid, &DIAGONAL);
+        assert_eq!(scenic_scores[(1, 1)], 3);
+        assert_eq!(
+            scan_line(&grid, (1, 1), (1, 1)).collect::<Vec<_>>(),
+            vec![(1, 1), (2, 2), (3, 3), (4, 4)]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn generate_test() -> Result<()> {
+        let input = generate(1, 20);
+        assert_eq!(input, generate(1, 20));
+        assert_ne!(input, generate(2, 20));
+        part1(&input)?;
+        part2(&input)?;
+
+        Ok(())
+    }
+
+    // looks from each tree in each direction on its own
+    fn naive_view(
+        grid: &Grid<u32>,
+        position: (usize, usize),
+        direction: (isize, isize),
+    ) -> (bool, u64) {
+        let height = grid[position];
+        let mut current = position;
+        let mut distance = 0;
+        while let Some(next) = grid.offset(current, direction) {
+            distance += 1;
+            if grid[next] >= height {
+                return (false, distance);
+            }
+            current = next;
+        }
+        (true, distance)
+    }
+
+    #[test]
+    fn naive_test() -> Result<()> {
+        for seed in 0..20 {
+            let grid = parse_grid(&generate(seed, seed as usize % 8 + 1))?;
+            for directions in [&ORTHOGONAL[..], &[ORTHOGONAL, DIAGONAL].concat()] {
+                let visibility_map = get_visibility_map(&grid, directions);
+                let scenic_scores = get_scenic_scores(&grid, directions);
+                for position in grid.positions() {
+                    let views: Vec<_> = directions
+                        .iter()
+                        .map(|d| naive_view(&grid, position, *d))
+                        .collect();
+                    assert_eq!(
+                        visibility_map[position],
+                        views.iter().any(|(visible, _)| *visible)
+                    );
+                    assert_eq!(
+                        scenic_scores[position],
+                        views.iter().map(|(_, distance)| distance).product()
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn fuzz_parse_grid_test() {
+        common::fuzz::fuzz(&[INPUT, &generate(0, 8)], |input| {
+            let _ = part1(input);
+            let _ = part2(input);
+            if let Ok(grid) = parse_grid(input) {
+                let directions = [ORTHOGONAL, DIAGONAL].concat();
+                get_visibility_map(&grid, &directions);
+                for mode in [
+                    RenderMode::Visibility,
+                    RenderMode::Heights,
+                    RenderMode::Scenic,
+                ] {
+                    render_text(&grid, mode, &directions);
+                }
+            }
+        });
+    }
+}
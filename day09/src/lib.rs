@@ -0,0 +1,848 @@
+use common::{Result, Rng};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::env;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+// everything the binary does, main.rs only calls this
+pub fn run() -> Result<()> {
+    // prints a random input instead of solving one
+    if let Some((seed, size)) = common::random::generate_flag()? {
+        println!("{}", generate(seed, size));
+        return Ok(());
+    }
+
+    // first arg is the name of the program
+    let path = match env::args().nth(1) {
+        Some(path) => path,
+        None => String::from("input.txt"),
+    };
+    // rest of the args are optional flags
+    let flags: Vec<String> = env::args().skip(2).collect();
+    let input = common::read_input(&path)?;
+
+    let print_steps = flags.iter().any(|f| f == "--steps");
+    let print_trace = flags.iter().any(|f| f == "--trace");
+    let frames_dir = flags.iter().find_map(|f| f.strip_prefix("--frames="));
+    // the real input has over ten thousand steps, so draw only some of them
+    let mut drawn_steps = DrawnSteps {
+        first: 1,
+        last: u32::MAX,
+        every: 1,
+    };
+    for flag in &flags {
+        if let Some(range) = flag.strip_prefix("--range=") {
+            let (first, last) = range.split_once('-').ok_or("expected --range=A-B")?;
+            drawn_steps.first = first.parse()?;
+            drawn_steps.last = last.parse()?;
+        } else if let Some(n) = flag.strip_prefix("--every=") {
+            drawn_steps.every = n.parse()?;
+            if drawn_steps.every == 0 {
+                return Err("--every needs to be at least 1".into());
+            }
+        }
+    }
+    if print_steps || print_trace || frames_dir.is_some() {
+        visualize(&input, print_steps, print_trace, frames_dir, &drawn_steps)?;
+    }
+
+    println!("{}", part1(&input)?);
+    println!("{}", part2(&input)?);
+    Ok(())
+}
+
+// both parts, for aoc run
+pub struct Solution;
+
+impl common::Day for Solution {
+    fn part1(input: &str) -> Result<String> {
+        part1(input)
+    }
+
+    fn part2(input: &str) -> Result<String> {
+        part2(input)
+    }
+}
+
+fn part1(input: &str) -> Result<String> {
+    let trail = simulate_bulk(input)?;
+    Ok(trail.len().to_string())
+}
+
+fn part2(_input: &str) -> Result<String> {
+    Err("not implemented".into())
+}
+
+// runs the whole input, calling on_step after every tick
+// returns all positions visited by the tail
+fn simulate(input: &str, mut on_step: impl FnMut(&State)) -> Result<HashSet<(i32, i32)>> {
+    let mut state: State = State {
+        head_position: (0, 0),
+        tail_position: (0, 0),
+    };
+    let mut positions_visited: HashSet<(i32, i32)> = HashSet::new();
+    positions_visited.insert((0, 0)); // also add initial position
+
+    for motion in parse_motions(input)? {
+        for _ in 0..motion.steps {
+            let (new_state, tail_moved_to) = tick(&state, motion.dir);
+            state = new_state;
+            if let Some(x) = tail_moved_to {
+                positions_visited.insert(x);
+            }
+            on_step(&state);
+        }
+    }
+    Ok(positions_visited)
+}
+
+// Same result as simulate, but moves the head a whole motion at a time.
+// The tail needs at most a few ticks to start following. After it has moved
+// once it stays right behind the head and takes the exact same step as the
+// head on every tick, so the rest of its path is a straight line.
+fn simulate_bulk(input: &str) -> Result<Trail> {
+    let mut state: State = State {
+        head_position: (0, 0),
+        tail_position: (0, 0),
+    };
+    let mut trail = Trail::default();
+    trail.push((0, 0), (1, 0), 1); // also add initial position
+
+    for motion in parse_motions(input)? {
+        let mut remaining = motion.steps;
+        let mut is_following = false;
+        while remaining > 0 && !is_following {
+            let (new_state, tail_moved_to) = tick(&state, motion.dir);
+            state = new_state;
+            remaining -= 1;
+            if let Some(x) = tail_moved_to {
+                trail.push(x, (1, 0), 1);
+                is_following = true;
+            }
+        }
+
+        if remaining > 0 {
+            let (dx, dy) = motion.dir.delta();
+            let (tail_x, tail_y) = state.tail_position;
+            let (head_x, head_y) = state.head_position;
+            let steps = remaining as i32;
+            trail.push((tail_x + dx, tail_y + dy), (dx, dy), remaining);
+            state = State {
+                head_position: (head_x + steps * dx, head_y + steps * dy),
+                tail_position: (tail_x + steps * dx, tail_y + steps * dy),
+            };
+        }
+    }
+    Ok(trail)
+}
+
+// Positions visited by the tail, kept as straight segments instead of single
+// cells so that motions millions of steps long stay cheap.
+#[derive(Default)]
+struct Trail {
+    segments: Vec<Segment>,
+}
+
+// cells start + k * step for k in 0..count
+struct Segment {
+    start: (i32, i32),
+    step: (i32, i32),
+    count: u32,
+}
+
+// A segment lies on one of four kinds of lines, written as a * x + b * y = k:
+// horizontal, vertical and the two diagonals.
+const LINE_KINDS: [(i64, i64); 4] = [(0, 1), (1, 0), (1, -1), (1, 1)];
+
+impl Trail {
+    fn push(&mut self, start: (i32, i32), step: (i32, i32), count: u32) {
+        if count > 0 {
+            self.segments.push(Segment { start, step, count });
+        }
+    }
+
+    fn cells(&self) -> HashSet<(i32, i32)> {
+        let mut cells: HashSet<(i32, i32)> = HashSet::new();
+        for segment in &self.segments {
+            let ((x, y), (dx, dy), count) = (segment.start, segment.step, segment.count);
+            cells.extend((0..count as i32).map(|k| (x + k * dx, y + k * dy)));
+        }
+        cells
+    }
+
+    // Amount of distinct cells without visiting them one by one. Segments on
+    // the same line are merged, which leaves only crossings between different
+    // kinds of lines counted more than once.
+    fn len(&self) -> u64 {
+        // for each line kind, k -> ranges along the line
+        let mut lines: [BTreeMap<i64, Vec<(i64, i64)>>; 4] = Default::default();
+        for segment in &self.segments {
+            let ((x, y), (dx, dy), count) = (segment.start, segment.step, segment.count);
+            let kind = match (dx, dy) {
+                _ if count == 1 => 0, // single cell, any kind works
+                (_, 0) => 0,
+                (0, _) => 1,
+                _ if dx == dy => 2,
+                _ => 3,
+            };
+            let (a, b) = LINE_KINDS[kind];
+            let (x, y) = (x as i64, y as i64);
+            let (end_x, end_y) = (
+                x + (count as i64 - 1) * dx as i64,
+                y + (count as i64 - 1) * dy as i64,
+            );
+            let k = a * x + b * y;
+            let range = (
+                line_position(kind, x, y).min(line_position(kind, end_x, end_y)),
+                line_position(kind, x, y).max(line_position(kind, end_x, end_y)),
+            );
+            lines[kind].entry(k).or_default().push(range);
+        }
+
+        // merged ranges stay sorted, so they can be searched below
+        let mut total = 0;
+        for ranges in lines.iter_mut().flat_map(|kind| kind.values_mut()) {
+            ranges.sort();
+            let mut merged: Vec<(i64, i64)> = Vec::with_capacity(ranges.len());
+            for &(start, end) in ranges.iter() {
+                match merged.last_mut() {
+                    Some((_, e)) if start <= *e + 1 => *e = (*e).max(end),
+                    _ => merged.push((start, end)),
+                }
+            }
+            total += merged.iter().map(|(s, e)| (e - s + 1) as u64).sum::<u64>();
+            *ranges = merged;
+        }
+
+        // Every cell where lines of different kinds cross was counted once per
+        // kind. Along a range of one kind, k of the other kind goes from its
+        // value at one end to its value at the other, so only the lines of the
+        // other kind with k in between can cross it.
+        let mut crossings: HashMap<(i64, i64), u8> = HashMap::new();
+        for kind1 in 0..LINE_KINDS.len() {
+            for kind2 in kind1 + 1..LINE_KINDS.len() {
+                let (a2, b2) = LINE_KINDS[kind2];
+                let k2_at = |k1: i64, position: i64| {
+                    let (x, y) = line_cell(kind1, k1, position);
+                    a2 * x + b2 * y
+                };
+                for (&k1, ranges1) in &lines[kind1] {
+                    for &(start1, end1) in ranges1 {
+                        let (from, to) = (k2_at(k1, start1), k2_at(k1, end1));
+                        for (&k2, ranges2) in lines[kind2].range(from.min(to)..=from.max(to)) {
+                            let Some((x, y)) = line_crossing(kind1, k1, kind2, k2) else {
+                                continue;
+                            };
+                            // last range starting at or before the crossing
+                            let position2 = line_position(kind2, x, y);
+                            let i = ranges2.partition_point(|(start, _)| *start <= position2);
+                            if i > 0 && ranges2[i - 1].1 >= position2 {
+                                *crossings.entry((x, y)).or_insert(0) |=
+                                    (1 << kind1) | (1 << kind2);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        let duplicates: u64 = crossings
+            .values()
+            .map(|kinds| kinds.count_ones() as u64 - 1)
+            .sum();
+
+        total - duplicates
+    }
+}
+
+// where along a line of the given kind the cell is
+fn line_position(kind: usize, x: i64, y: i64) -> i64 {
+    if kind == 1 {
+        y
+    } else {
+        x
+    }
+}
+
+// the cell at a position along a line, the opposite of line_position
+fn line_cell(kind: usize, k: i64, position: i64) -> (i64, i64) {
+    match kind {
+        0 => (position, k),
+        1 => (k, position),
+        2 => (position, position - k),
+        _ => (position, k - position),
+    }
+}
+
+// the cell where two lines cross, if they cross exactly on a cell
+fn line_crossing(kind1: usize, k1: i64, kind2: usize, k2: i64) -> Option<(i64, i64)> {
+    let (a1, b1) = LINE_KINDS[kind1];
+    let (a2, b2) = LINE_KINDS[kind2];
+    // Cramer's rule
+    let determinant = a1 * b2 - a2 * b1;
+    let x = k1 * b2 - k2 * b1;
+    let y = a1 * k2 - a2 * k1;
+    if x % determinant == 0 && y % determinant == 0 {
+        Some((x / determinant, y / determinant))
+    } else {
+        None
+    }
+}
+
+// Steps drawn by --steps and --frames: every nth step from first to last,
+// counting from 1.
+struct DrawnSteps {
+    first: u32,
+    last: u32,
+    every: u32,
+}
+
+impl DrawnSteps {
+    fn contains(&self, step: u32) -> bool {
+        (self.first..=self.last).contains(&step) && (step - self.first).is_multiple_of(self.every)
+    }
+}
+
+fn visualize(
+    input: &str,
+    print_steps: bool,
+    print_trace: bool,
+    frames_dir: Option<&str>,
+    drawn_steps: &DrawnSteps,
+) -> Result<()> {
+    // first run only to find out how large the picture needs to be
+    let mut bounds = Bounds::new((0, 0));
+    simulate(input, |state| bounds.include(state.head_position))?;
+    let visited = simulate_bulk(input)?.cells();
+
+    let mut step = 0;
+    let mut frame = 0;
+    let mut frame_result: Result<()> = Ok(());
+    simulate(input, |state| {
+        step += 1;
+        if !drawn_steps.contains(step) {
+            return;
+        }
+        if print_steps {
+            println!("{}\n", render(&bounds, None, Some(state)));
+        }
+        if let (Some(dir), Ok(())) = (frames_dir, &frame_result) {
+            let path = Path::new(dir).join(format!("frame_{frame:05}.ppm"));
+            frame_result = write_ppm(&path, &bounds, None, Some(state));
+            frame += 1;
+        }
+    })?;
+    frame_result?;
+
+    if print_trace {
+        println!("{}", render(&bounds, Some(&visited), None));
+    }
+    if let Some(dir) = frames_dir {
+        let path = Path::new(dir).join("visited.ppm");
+        write_ppm(&path, &bounds, Some(&visited), None)?;
+    }
+    Ok(())
+}
+
+// area covered by the drawings, inclusive
+struct Bounds {
+    min: (i32, i32),
+    max: (i32, i32),
+}
+
+impl Bounds {
+    fn new(position: (i32, i32)) -> Bounds {
+        Bounds {
+            min: position,
+            max: position,
+        }
+    }
+
+    fn include(&mut self, (x, y): (i32, i32)) {
+        self.min = (self.min.0.min(x), self.min.1.min(y));
+        self.max = (self.max.0.max(x), self.max.1.max(y));
+    }
+
+    // top row first, since up is +y like in the puzzle's pictures
+    fn rows(&self) -> impl Iterator<Item = i32> {
+        (self.min.1..=self.max.1).rev()
+    }
+
+    fn columns(&self) -> impl Iterator<Item = i32> {
+        self.min.0..=self.max.0
+    }
+}
+
+// what is drawn on one cell, later ones are drawn over earlier ones
+#[derive(Clone, Copy, PartialEq)]
+enum Cell {
+    Empty,
+    Visited,
+    Start,
+    Tail,
+    Head,
+}
+
+fn get_cell(
+    position: (i32, i32),
+    visited: Option<&HashSet<(i32, i32)>>,
+    rope: Option<&State>,
+) -> Cell {
+    match rope {
+        Some(state) if state.head_position == position => Cell::Head,
+        Some(state) if state.tail_position == position => Cell::Tail,
+        _ if position == (0, 0) => Cell::Start,
+        _ if visited.is_some_and(|v| v.contains(&position)) => Cell::Visited,
+        _ => Cell::Empty,
+    }
+}
+
+// Same style as the puzzle's pictures: H and T for the rope, s for the start
+// and # for positions visited by the tail.
+fn render(bounds: &Bounds, visited: Option<&HashSet<(i32, i32)>>, rope: Option<&State>) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    for y in bounds.rows() {
+        let line: String = bounds
+            .columns()
+            .map(|x| match get_cell((x, y), visited, rope) {
+                Cell::Empty => '.',
+                Cell::Visited => '#',
+                Cell::Start => 's',
+                Cell::Tail => 'T',
+                Cell::Head => 'H',
+            })
+            .collect();
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+// binary PPM image, each cell is a few pixels wide so small ropes are visible
+fn write_ppm(
+    path: &Path,
+    bounds: &Bounds,
+    visited: Option<&HashSet<(i32, i32)>>,
+    rope: Option<&State>,
+) -> Result<()> {
+    const CELL_SIZE: usize = 4;
+
+    // the bounds can be almost as far apart as the whole i32 range
+    let pixels = |min: i32, max: i32| {
+        max.checked_sub(min)
+            .and_then(|cells| usize::try_from(cells).ok()?.checked_add(1))
+            .and_then(|cells| cells.checked_mul(CELL_SIZE))
+    };
+    let (width, height) = pixels(bounds.min.0, bounds.max.0)
+        .zip(pixels(bounds.min.1, bounds.max.1))
+        .filter(|(width, height)| {
+            width
+                .checked_mul(3)
+                .and_then(|row| row.checked_mul(*height))
+                .is_some()
+        })
+        .ok_or("picture too large")?;
+    let mut data = format!("P6\n{width} {height}\n255\n").into_bytes();
+    for y in bounds.rows() {
+        let mut row: Vec<u8> = Vec::with_capacity(width * 3);
+        for x in bounds.columns() {
+            let color: [u8; 3] = match get_cell((x, y), visited, rope) {
+                Cell::Empty => [0, 0, 0],
+                Cell::Visited => [128, 128, 128],
+                Cell::Start => [0, 200, 0],
+                Cell::Tail => [60, 60, 255],
+                Cell::Head => [255, 40, 40],
+            };
+            for _ in 0..CELL_SIZE {
+                row.extend(color);
+            }
+        }
+        for _ in 0..CELL_SIZE {
+            data.extend(&row);
+        }
+    }
+    File::create(path)?.write_all(&data)?;
+    Ok(())
+}
+
+struct State {
+    head_position: (i32, i32),
+    tail_position: (i32, i32),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+    // diagonals aren't in the puzzle, but the rope handles them just fine
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    fn parse(src: &str) -> Option<Direction> {
+        match src {
+            "U" => Some(Direction::Up),
+            "R" => Some(Direction::Right),
+            "D" => Some(Direction::Down),
+            "L" => Some(Direction::Left),
+            "UL" => Some(Direction::UpLeft),
+            "UR" => Some(Direction::UpRight),
+            "DL" => Some(Direction::DownLeft),
+            "DR" => Some(Direction::DownRight),
+            _ => None,
+        }
+    }
+
+    // (dx, dy), up is +y
+    fn delta(self) -> (i32, i32) {
+        match self {
+            Direction::Up => (0, 1),
+            Direction::Right => (1, 0),
+            Direction::Down => (0, -1),
+            Direction::Left => (-1, 0),
+            Direction::UpLeft => (-1, 1),
+            Direction::UpRight => (1, 1),
+            Direction::DownLeft => (-1, -1),
+            Direction::DownRight => (1, -1),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Motion {
+    dir: Direction,
+    steps: u32,
+}
+
+fn parse_motions(input: &str) -> Result<Vec<Motion>> {
+    let mut motions: Vec<Motion> = Vec::new();
+    // positions are i32, so the rope can't be allowed to get any further
+    let mut total_steps: u64 = 0;
+    for (i, line) in input.lines().enumerate() {
+        let line_number = i + 1; // for error messages
+        let (dir, steps) = line.split_once(' ').ok_or_else(|| {
+            format!("line {line_number}: expected motion like \"R 4\", got {line:?}")
+        })?;
+        let dir = Direction::parse(dir)
+            .ok_or_else(|| format!("line {line_number}: unknown direction {dir:?}"))?;
+        let steps: u32 = steps
+            .parse()
+            .map_err(|_| format!("line {line_number}: invalid step count {steps:?}"))?;
+        total_steps += u64::from(steps);
+        if total_steps > i32::MAX as u64 {
+            return Err(format!("line {line_number}: too many steps in total").into());
+        }
+        motions.push(Motion { dir, steps });
+    }
+    Ok(motions)
+}
+
+// advances simulation by one tick
+// return new state and "events", in this case position if the tail moved
+fn tick(state: &State, direction: Direction) -> (State, Option<(i32, i32)>) {
+    let (move_x, move_y) = direction.delta();
+    let head_position = (
+        state.head_position.0 + move_x,
+        state.head_position.1 + move_y,
+    );
+
+    let dx = head_position.0 - state.tail_position.0;
+    let dy = head_position.1 - state.tail_position.1;
+
+    // Tail takes one step towards the head, diagonally if needed. For
+    // straight head moves that's the same as moving to where the head was,
+    // but not when the head itself moves diagonally.
+    let tail_moved_to = if dx.abs() > 1 || dy.abs() > 1 {
+        Some((
+            state.tail_position.0 + dx.signum(),
+            state.tail_position.1 + dy.signum(),
+        ))
+    } else {
+        None
+    };
+    let tail_position = tail_moved_to.unwrap_or(state.tail_position);
+
+    (
+        State {
+            head_position,
+            tail_position,
+        },
+        tail_moved_to,
+    )
+}
+
+// size is the amount of motions
+fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let motions: Vec<String> = (0..size)
+        .map(|_| {
+            format!(
+                "{} {}",
+                rng.choose(&['U', 'R', 'D', 'L']),
+                rng.range(1..=20)
+            )
+        })
+        .collect();
+    motions.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::io::Read;
+
+    const INPUT: &str = include_str!("../examples/example.txt");
+
+    // example_* tests, generated by build.rs from examples/manifest
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+
+    #[test]
+    fn parse_motions_test() -> Result<()> {
+        let motions = parse_motions("R 4\nUL 2")?;
+        assert_eq!(
+            motions,
+            vec![
+                Motion {
+                    dir: Direction::Right,
+                    steps: 4
+                },
+                Motion {
+                    dir: Direction::UpLeft,
+                    steps: 2
+                }
+            ]
+        );
+
+        let error = parse_motions("R 4\nX 2").unwrap_err();
+        assert_eq!(error.to_string(), "line 2: unknown direction \"X\"");
+        let error = parse_motions("R -4").unwrap_err();
+        assert_eq!(error.to_string(), "line 1: invalid step count \"-4\"");
+        assert!(parse_motions("R4").is_err());
+        let error = parse_motions("R 2147483647\nL 1").unwrap_err();
+        assert_eq!(error.to_string(), "line 2: too many steps in total");
+
+        Ok(())
+    }
+
+    #[test]
+    fn diagonal_test() -> Result<()> {
+        // head goes up-right past the tail, which has to cut the corner
+        let input = concat!("R 1\n", "UR 2");
+        let mut states: Vec<((i32, i32), (i32, i32))> = Vec::new();
+        simulate(input, |s| states.push((s.head_position, s.tail_position)))?;
+        assert_eq!(
+            states,
+            vec![((1, 0), (0, 0)), ((2, 1), (1, 1)), ((3, 2), (2, 2))]
+        );
+        assert_eq!(part1(input)?, "3");
+
+        Ok(())
+    }
+
+    #[test]
+    fn simulate_bulk_test() -> Result<()> {
+        let expected = simulate(INPUT, |_| ())?;
+        let trail = simulate_bulk(INPUT)?;
+        assert_eq!(trail.cells(), expected);
+        assert_eq!(trail.len(), expected.len() as u64);
+
+        // every way the tail can touch the head, followed by every motion
+        let directions = ["U", "R", "D", "L", "UL", "UR", "DL", "DR"];
+        for (x, y) in [-1, 0, 1].iter().flat_map(|x| [-1, 0, 1].map(|y| (*x, y))) {
+            // the head starts at (0, 0), this moves it to (x, y) from the tail
+            let setup = match (x, y) {
+                (0, 0) => String::new(),
+                _ => {
+                    let dir = directions
+                        .iter()
+                        .find(|d| Direction::parse(d).unwrap().delta() == (x, y))
+                        .unwrap();
+                    format!("{dir} 1\n")
+                }
+            };
+            for dir in directions {
+                for steps in 0..5 {
+                    let input = format!("{setup}{dir} {steps}");
+                    let expected = simulate(&input, |_| ())?;
+                    let trail = simulate_bulk(&input)?;
+                    assert_eq!(trail.cells(), expected, "{input}");
+                    assert_eq!(trail.len(), expected.len() as u64, "{input}");
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn trail_len_test() {
+        // a star of lines through the origin, crossing a square around it
+        let mut trail = Trail::default();
+        for (dx, dy) in [(1, 0), (0, 1), (1, 1), (1, -1)] {
+            trail.push((-3 * dx, -3 * dy), (dx, dy), 7);
+        }
+        trail.push((-2, -2), (1, 0), 5);
+        trail.push((-2, 2), (1, 0), 5);
+        trail.push((-2, -2), (0, 1), 5);
+        trail.push((2, -2), (0, 1), 5);
+        assert_eq!(trail.len(), trail.cells().len() as u64);
+
+        // diagonals crossing between cells
+        let mut trail = Trail::default();
+        trail.push((0, 0), (1, 1), 4);
+        trail.push((0, 1), (1, -1), 2);
+        assert_eq!(trail.len(), 6);
+        assert_eq!(trail.cells().len(), 6);
+    }
+
+    #[test]
+    fn render_test() -> Result<()> {
+        let mut bounds = Bounds::new((0, 0));
+        let mut last_frame = String::new();
+        let visited = simulate(INPUT, |state| bounds.include(state.head_position))?;
+        simulate(INPUT, |state| {
+            last_frame = render(&bounds, None, Some(state))
+        })?;
+
+        let expected = concat!("......\n", "......\n", ".TH...\n", "......\n", "s.....");
+        assert_eq!(last_frame, expected);
+        let expected = concat!("..##..\n", "...##.\n", ".####.\n", "....#.\n", "s###..");
+        assert_eq!(render(&bounds, Some(&visited), None), expected);
+
+        let path = env::temp_dir().join(format!("day09_render_test_{}.ppm", std::process::id()));
+        write_ppm(&path, &bounds, Some(&visited), None)?;
+        let mut data: Vec<u8> = Vec::new();
+        File::open(&path)?.read_to_end(&mut data)?;
+        fs::remove_file(&path)?;
+        assert!(data.starts_with(b"P6\n24 20\n255\n"));
+        assert_eq!(data.len(), 13 + 24 * 20 * 3);
+
+        // only checked, never written
+        let huge = Bounds {
+            min: (i32::MIN, 0),
+            max: (i32::MAX, 0),
+        };
+        assert!(write_ppm(&path, &huge, None, None).is_err());
+        let wide = Bounds {
+            min: (0, 0),
+            max: (i32::MAX - 1, i32::MAX - 1),
+        };
+        assert!(write_ppm(&path, &wide, None, None).is_err());
+        assert!(!path.exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn frames_test() -> Result<()> {
+        let dir = env::temp_dir().join(format!("day09_frames_test_{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let drawn_steps = DrawnSteps {
+            first: 3,
+            last: 20,
+            every: 5,
+        };
+        let result = visualize(INPUT, false, false, dir.to_str(), &drawn_steps);
+        let mut files: Vec<String> = fs::read_dir(&dir)?
+            .map(|entry| Ok(entry?.file_name().to_string_lossy().into_owned()))
+            .collect::<Result<_>>()?;
+        fs::remove_dir_all(&dir)?;
+        result?;
+
+        // steps 3, 8, 13 and 18 of the 24
+        files.sort();
+        let expected = [
+            "frame_00000.ppm",
+            "frame_00001.ppm",
+            "frame_00002.ppm",
+            "frame_00003.ppm",
+            "visited.ppm",
+        ];
+        assert_eq!(files, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn generate_test() -> Result<()> {
+        let input = generate(1, 100);
+        assert_eq!(input, generate(1, 100));
+        assert_ne!(input, generate(2, 100));
+        part1(&input)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn many_short_motions_test() -> Result<()> {
+        // a random walk of short segments, most of them crossing others
+        let mut rng = Rng::new(0);
+        let directions = ["U", "R", "D", "L", "UL", "UR", "DL", "DR"];
+        let motions: Vec<String> = (0..100_000)
+            .map(|_| format!("{} {}", rng.choose(&directions), rng.range(1..=3)))
+            .collect();
+        let input = motions.join("\n");
+        let expected = simulate(&input, |_| ())?;
+        assert_eq!(simulate_bulk(&input)?.len(), expected.len() as u64);
+
+        Ok(())
+    }
+
+    #[test]
+    fn generated_trail_test() -> Result<()> {
+        for seed in 0..30 {
+            let input = generate(seed, seed as usize * 5);
+            let expected = simulate(&input, |_| ())?;
+            let trail = simulate_bulk(&input)?;
+            assert_eq!(trail.cells(), expected);
+            assert_eq!(trail.len(), expected.len() as u64);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn fuzz_parse_motions_test() {
+        common::fuzz::fuzz(&[INPUT, &generate(0, 10)], |input| {
+            let _ = part1(input);
+        });
+    }
+
+    // simulate goes one step at a time, too slow for the huge step counts
+    fn is_short(input: &str) -> bool {
+        match parse_motions(input) {
+            Ok(motions) => motions.iter().map(|m| m.steps).sum::<u32>() <= 200,
+            Err(_) => true,
+        }
+    }
+
+    #[test]
+    fn fuzz_simulate_test() {
+        common::fuzz::fuzz(&[INPUT, &generate(0, 10)], |input| {
+            if is_short(input) {
+                if let Ok(visited) = simulate(input, |_| ()) {
+                    assert_eq!(Some(visited), simulate_bulk(input).ok().map(|t| t.cells()));
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn fuzz_visualize_test() {
+        common::fuzz::fuzz(&[INPUT, &generate(0, 10)], |input| {
+            if is_short(input) {
+                let every_step = DrawnSteps {
+                    first: 1,
+                    last: u32::MAX,
+                    every: 1,
+                };
+                let _ = visualize(input, false, true, None, &every_step);
+            }
+        });
+    }
+}
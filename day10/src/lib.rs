@@ -0,0 +1,137 @@
+use common::{Result, Rng};
+use std::env;
+
+// everything the binary does, main.rs only calls this
+pub fn run() -> Result<()> {
+    // prints a random input instead of solving one
+    if let Some((seed, size)) = common::random::generate_flag()? {
+        println!("{}", generate(seed, size));
+        return Ok(());
+    }
+
+    // first arg is the name of the program
+    let path = match env::args().nth(1) {
+        Some(path) => path,
+        None => String::from("input.txt"),
+    };
+    let input = common::read_input(&path)?;
+
+    println!("{}", part1(&input)?);
+    println!("{}", part2(&input)?);
+    Ok(())
+}
+
+enum Instruction {
+    Noop,
+    Addx(i32),
+}
+
+fn parse_instructions(input: &str) -> Result<Vec<Instruction>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let mut split = line.split(' ');
+            match (split.next(), split.next(), split.next()) {
+                (Some("noop"), None, None) => Ok(Instruction::Noop),
+                (Some("addx"), Some(v), None) => Ok(Instruction::Addx(v.parse()?)),
+                _ => Err(format!("line {}: unknown instruction {line:?}", i + 1).into()),
+            }
+        })
+        .collect()
+}
+
+// both parts, for aoc run
+pub struct Solution;
+
+impl common::Day for Solution {
+    fn part1(input: &str) -> Result<String> {
+        part1(input)
+    }
+
+    fn part2(input: &str) -> Result<String> {
+        part2(input)
+    }
+}
+
+fn part1(input: &str) -> Result<String> {
+    let mut cycles: i64 = 0;
+    let mut reg_x: i64 = 1;
+    let mut signal_strengths: Vec<i64> = Vec::new();
+
+    let mut tick = |x: i64| -> Result<()> {
+        cycles += 1;
+        if cycles % 40 == 19 {
+            let signal_strength = x.checked_mul(cycles + 1).ok_or("signal too strong")?;
+            signal_strengths.push(signal_strength);
+        }
+        Ok(())
+    };
+
+    for instruction in parse_instructions(input)? {
+        match instruction {
+            Instruction::Noop => {
+                tick(reg_x)?;
+            }
+            Instruction::Addx(v) => {
+                tick(reg_x)?;
+                reg_x = reg_x.checked_add(v.into()).ok_or("x register overflow")?;
+                tick(reg_x)?;
+            }
+        };
+    }
+    let sum = signal_strengths
+        .iter()
+        .try_fold(0i64, |sum, x| sum.checked_add(*x))
+        .ok_or("signal too strong")?;
+    Ok(sum.to_string())
+}
+
+fn part2(_input: &str) -> Result<String> {
+    Err("not implemented".into())
+}
+
+// Size is the amount of instructions, padded with noops to the 240 cycles
+// the screen needs.
+fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut lines: Vec<String> = Vec::new();
+    let mut cycles = 0;
+    while lines.len() < size || cycles < 240 {
+        if lines.len() < size && rng.percent(60) {
+            lines.push(format!("addx {}", rng.range(-10..=10)));
+            cycles += 2;
+        } else {
+            lines.push(String::from("noop"));
+            cycles += 1;
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("../examples/example.txt");
+
+    // example_* tests, generated by build.rs from examples/manifest
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+
+    #[test]
+    fn generate_test() -> Result<()> {
+        let input = generate(1, 50);
+        assert_eq!(input, generate(1, 50));
+        assert_ne!(input, generate(2, 50));
+        part1(&input)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn fuzz_parse_instructions_test() {
+        common::fuzz::fuzz(&[INPUT, &generate(0, 10)], |input| {
+            let _ = part1(input);
+        });
+    }
+}
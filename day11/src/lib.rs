@@ -0,0 +1,1287 @@
+use common::{Result, Rng};
+use lazy_static::lazy_static;
+use num_bigint::BigInt;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fmt;
+use std::fs;
+
+// everything the binary does, main.rs only calls this
+pub fn run() -> Result<()> {
+    // prints a random input instead of solving one
+    if let Some((seed, size)) = common::random::generate_flag()? {
+        println!("{}", generate(seed, size));
+        return Ok(());
+    }
+
+    // first arg is the name of the program
+    let path = match env::args().nth(1) {
+        Some(path) => path,
+        None => String::from("input.txt"),
+    };
+    // rest of the args are optional flags
+    let flags: Vec<String> = env::args().skip(2).collect();
+    let input = common::read_input(&path)?;
+
+    println!("{}", part1(&input)?);
+    println!("{}", part2(&input)?);
+
+    // part 2 rules for any amount of rounds, even 1000000000000
+    if let Some(rounds) = flags.iter().find_map(|f| f.strip_prefix("--rounds=")) {
+        let troop = parse_troop(&input)?;
+        let activity = count_inspections_by_cycles(&troop, rounds.parse()?);
+        println!("{}", monke_business(&activity));
+    }
+    // part 2 rules with exact worry levels, only feasible for a few rounds
+    if let Some(rounds) = flags.iter().find_map(|f| f.strip_prefix("--exact=")) {
+        let troop = parse_troop(&input)?;
+        let activity = simulate::<BigInt>(&troop, rounds.parse()?, Relief::None);
+        println!("{}", monke_business(&activity));
+    }
+
+    // logs and csv follow the part 1 rules unless --no-relief asks for part 2
+    let (rounds, relief) = if flags.iter().any(|f| f == "--no-relief") {
+        (10000, Relief::None)
+    } else {
+        (20, Relief::DivideByThree)
+    };
+    if let Some(log_rounds) = flags.iter().find_map(|f| f.strip_prefix("--log=")) {
+        let log_rounds: Vec<u64> = log_rounds
+            .split(',')
+            .map(str::parse)
+            .collect::<std::result::Result<_, _>>()?;
+        print!(
+            "{}",
+            inspection_log(&parse_troop(&input)?, rounds, relief, &log_rounds)
+        );
+    }
+    if let Some(csv_path) = flags.iter().find_map(|f| f.strip_prefix("--csv=")) {
+        fs::write(
+            csv_path,
+            activity_csv(&parse_troop(&input)?, rounds, relief),
+        )?;
+    }
+    Ok(())
+}
+
+// both parts, for aoc run
+pub struct Solution;
+
+impl common::Day for Solution {
+    fn part1(input: &str) -> Result<String> {
+        part1(input)
+    }
+
+    fn part2(input: &str) -> Result<String> {
+        part2(input)
+    }
+}
+
+fn part1(input: &str) -> Result<String> {
+    let troop = parse_troop(input)?;
+    let activity = simulate_checked(&troop, 20)?;
+    Ok(monke_business(&activity).to_string())
+}
+
+fn part2(input: &str) -> Result<String> {
+    let troop = parse_troop(input)?;
+    let activity = simulate::<i64>(&troop, 10000, Relief::None);
+    Ok(monke_business(&activity).to_string())
+}
+
+#[derive(Clone, Copy)]
+enum Relief {
+    DivideByThree,
+    // Worry levels grow without bounds, but only their divisibility by the
+    // test divisors matters. Keeping them modulo the least common multiple of
+    // the divisors keeps every test result the same.
+    None,
+}
+
+// How worry levels are stored during the simulation. i64 is the fast one,
+// BigInt never reduces anything and is there to check the i64 results.
+trait Worry: Clone + fmt::Display {
+    fn from_item(item: i64) -> Self;
+    // applies the operation, with Relief::None i64 also reduces the result
+    fn operate(&self, operation: &Operation, relief: Relief, modulus: i64) -> Self;
+    fn relieve(&self) -> Self;
+    fn is_divisible_by(&self, divisor: i64) -> bool;
+}
+
+impl Worry for i64 {
+    fn from_item(item: i64) -> i64 {
+        item
+    }
+
+    fn operate(&self, operation: &Operation, relief: Relief, modulus: i64) -> i64 {
+        match relief {
+            Relief::DivideByThree => evaluate(operation, *self),
+            Relief::None => evaluate_modulo(operation, *self, modulus),
+        }
+    }
+
+    fn relieve(&self) -> i64 {
+        self / 3
+    }
+
+    fn is_divisible_by(&self, divisor: i64) -> bool {
+        self % divisor == 0
+    }
+}
+
+impl Worry for BigInt {
+    fn from_item(item: i64) -> BigInt {
+        BigInt::from(item)
+    }
+
+    fn operate(&self, operation: &Operation, _relief: Relief, _modulus: i64) -> BigInt {
+        evaluate_exact(operation, self)
+    }
+
+    fn relieve(&self) -> BigInt {
+        self / 3
+    }
+
+    fn is_divisible_by(&self, divisor: i64) -> bool {
+        (self % divisor) == BigInt::from(0)
+    }
+}
+
+// i64 that remembers when it overflowed, for checking that a troop fits in i64
+#[derive(Clone, Copy)]
+struct Checked(Option<i64>);
+
+impl fmt::Display for Checked {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(x) => write!(f, "{x}"),
+            None => write!(f, "overflow"),
+        }
+    }
+}
+
+impl Worry for Checked {
+    fn from_item(item: i64) -> Checked {
+        Checked(Some(item))
+    }
+
+    fn operate(&self, operation: &Operation, relief: Relief, modulus: i64) -> Checked {
+        Checked(self.0.and_then(|x| match relief {
+            Relief::DivideByThree => evaluate_checked(operation, x),
+            Relief::None => Some(evaluate_modulo(operation, x, modulus)),
+        }))
+    }
+
+    fn relieve(&self) -> Checked {
+        Checked(self.0.map(|x| x / 3))
+    }
+
+    fn is_divisible_by(&self, divisor: i64) -> bool {
+        self.0.is_some_and(|x| x % divisor == 0)
+    }
+}
+
+// what happens during a simulation, for logging
+enum Event<'a, W> {
+    Turn {
+        round: u64,
+        monke: &'a Monke,
+    },
+    Inspect {
+        round: u64,
+        monke: &'a Monke,
+        before: &'a W,
+        // after the operation but before the relief
+        operated: &'a W,
+        after: &'a W,
+        target: u32,
+    },
+    EndOfRound {
+        round: u64,
+        // in the same order as Troop::monkes
+        items: &'a [Vec<W>],
+        activity: &'a [u64],
+    },
+}
+
+// returns how many items each monke inspected
+fn simulate<W: Worry>(troop: &Troop, rounds: u64, relief: Relief) -> Vec<u64> {
+    simulate_with::<W>(troop, rounds, relief, |_| ())
+}
+
+// Part 1 rules, where nothing keeps the worry levels small. Errors instead of
+// overflowing.
+fn simulate_checked(troop: &Troop, rounds: u64) -> Result<Vec<u64>> {
+    let mut overflows = false;
+    let activity = simulate_with::<Checked>(troop, rounds, Relief::DivideByThree, |event| {
+        if let Event::Inspect { after, .. } = event {
+            overflows |= after.0.is_none();
+        }
+    });
+    if overflows {
+        return Err("worry levels don't fit in i64".into());
+    }
+    Ok(activity)
+}
+
+// same as simulate, but calls on_event for everything that happens
+fn simulate_with<W: Worry>(
+    troop: &Troop,
+    rounds: u64,
+    relief: Relief,
+    mut on_event: impl FnMut(Event<W>),
+) -> Vec<u64> {
+    let monkes = &troop.monkes;
+    let modulus = troop.modulus;
+    let mut monke_items: Vec<Vec<W>> = troop
+        .items
+        .iter()
+        .map(|items| items.iter().map(|item| W::from_item(*item)).collect())
+        .collect();
+    let mut activity: Vec<u64> = vec![0; monkes.len()];
+
+    // rounds are numbered from 1 like in the puzzle
+    for round in 1..=rounds {
+        for (i, monke) in monkes.iter().enumerate() {
+            on_event(Event::Turn { round, monke });
+            let items = std::mem::take(&mut monke_items[i]);
+
+            for worry_level in items {
+                let operated = worry_level.operate(&monke.operation, relief, modulus);
+                let after = match relief {
+                    Relief::DivideByThree => operated.relieve(),
+                    Relief::None => operated.clone(),
+                };
+                let target = if after.is_divisible_by(monke.test_divisible) {
+                    monke.test_true_monke
+                } else {
+                    monke.test_false_monke
+                };
+                on_event(Event::Inspect {
+                    round,
+                    monke,
+                    before: &worry_level,
+                    operated: &operated,
+                    after: &after,
+                    target,
+                });
+                monke_items[troop.index[&target]].push(after);
+                activity[i] += 1;
+            }
+        }
+        on_event(Event::EndOfRound {
+            round,
+            items: &monke_items,
+            activity: &activity,
+        });
+    }
+
+    activity
+}
+
+// Puzzle style description of the chosen rounds, like
+// "Monkey inspects an item with a worry level of 79."
+fn inspection_log(troop: &Troop, rounds: u64, relief: Relief, log_rounds: &[u64]) -> String {
+    let modulus = troop.modulus;
+    let mut log = String::new();
+
+    // worry levels that got too large are logged as "overflow"
+    simulate_with::<Checked>(troop, rounds, relief, |event| match event {
+        Event::Turn { round, monke } if log_rounds.contains(&round) => {
+            log += &format!("Monkey {}:\n", monke.id);
+        }
+        Event::Inspect {
+            round,
+            monke,
+            before,
+            operated,
+            after,
+            target,
+        } if log_rounds.contains(&round) => {
+            log += &format!("  Monkey inspects an item with a worry level of {before}.\n");
+            let change = describe_operation(&monke.operation);
+            log += &match relief {
+                Relief::DivideByThree => format!("    Worry level {change} to {operated}.\n"),
+                Relief::None => {
+                    format!("    Worry level {change} to {operated} (mod {modulus}).\n")
+                }
+            };
+            if let Relief::DivideByThree = relief {
+                log += &format!(
+                    "    Monkey gets bored with item. Worry level is divided by 3 to {after}.\n"
+                );
+            }
+            let not = if after.is_divisible_by(monke.test_divisible) {
+                ""
+            } else {
+                "not "
+            };
+            log += &format!(
+                "    Current worry level is {not}divisible by {}.\n",
+                monke.test_divisible
+            );
+            log += &format!("    Item with worry level {after} is thrown to monkey {target}.\n");
+        }
+        Event::EndOfRound {
+            round,
+            items,
+            activity,
+        } if log_rounds.contains(&round) => {
+            log += &format!(
+                "\nAfter round {round}, the monkeys are holding items with these worry levels:\n"
+            );
+            for (monke, items) in troop.monkes.iter().zip(items) {
+                let items: Vec<String> = items.iter().map(|x| x.to_string()).collect();
+                log += &format!("Monkey {}: {}\n", monke.id, items.join(", "));
+            }
+            log += "\n";
+            for (monke, count) in troop.monkes.iter().zip(activity) {
+                log += &format!("Monkey {} inspected items {count} times.\n", monke.id);
+            }
+            log += "\n";
+        }
+        _ => (),
+    });
+
+    log
+}
+
+// the words the puzzle uses for the common operations
+fn describe_operation(operation: &Operation) -> String {
+    use Operation::*;
+    match operation {
+        Add(lhs, rhs) if **lhs == Old => match &**rhs {
+            Old => String::from("is doubled"),
+            Constant(x) => format!("increases by {x}"),
+            _ => format!("becomes {operation}"),
+        },
+        Subtract(lhs, rhs) if **lhs == Old => match &**rhs {
+            Constant(x) => format!("decreases by {x}"),
+            _ => format!("becomes {operation}"),
+        },
+        Multiply(lhs, rhs) if **lhs == Old => match &**rhs {
+            Old => String::from("is multiplied by itself"),
+            Constant(x) => format!("is multiplied by {x}"),
+            _ => format!("becomes {operation}"),
+        },
+        _ => format!("becomes {operation}"),
+    }
+}
+
+// one row per monke and round, round 0 is before anyone did anything
+fn activity_csv(troop: &Troop, rounds: u64, relief: Relief) -> String {
+    let mut csv = String::from("round,monkey,item_count,items,activity\n");
+    for (monke, items) in troop.monkes.iter().zip(&troop.items) {
+        let items: Vec<String> = items.iter().map(|x| x.to_string()).collect();
+        csv += &format!("0,{},{},{},0\n", monke.id, items.len(), items.join(" "));
+    }
+
+    simulate_with::<Checked>(troop, rounds, relief, |event| {
+        if let Event::EndOfRound {
+            round,
+            items,
+            activity,
+        } = event
+        {
+            for ((monke, items), count) in troop.monkes.iter().zip(items).zip(activity) {
+                let item_list: Vec<String> = items.iter().map(|x| x.to_string()).collect();
+                csv += &format!(
+                    "{round},{},{},{},{count}\n",
+                    monke.id,
+                    items.len(),
+                    item_list.join(" ")
+                );
+            }
+        }
+    });
+
+    csv
+}
+
+// Every item moves through the monkes on its own, and with Relief::None its
+// whole future only depends on which monke holds it and its worry level
+// modulo the divisors. There are finitely many such states, so each item ends
+// up in a cycle. Follow each item until its state repeats, then the rest of
+// the rounds are just the same cycle over and over.
+fn count_inspections_by_cycles(troop: &Troop, rounds: u64) -> Vec<u64> {
+    let modulus = troop.modulus;
+    let mut activity: Vec<u64> = vec![0; troop.monkes.len()];
+
+    for (start, items) in troop.items.iter().enumerate() {
+        for &item in items {
+            // (monke, round) of every inspection so far
+            let mut path: Vec<(usize, u64)> = Vec::new();
+            // (monke, worry level) -> index in path
+            let mut seen: HashMap<(usize, i64), usize> = HashMap::new();
+            let mut monke_index = start;
+            let mut worry_level = item.rem_euclid(modulus);
+            let mut round = 0;
+
+            let cycle_start = loop {
+                if round >= rounds {
+                    break None;
+                }
+                if let Some(&i) = seen.get(&(monke_index, worry_level)) {
+                    break Some(i);
+                }
+                seen.insert((monke_index, worry_level), path.len());
+                path.push((monke_index, round));
+
+                let monke = &troop.monkes[monke_index];
+                worry_level = evaluate_modulo(&monke.operation, worry_level, modulus);
+                let target_monke_id = if worry_level % monke.test_divisible == 0 {
+                    monke.test_true_monke
+                } else {
+                    monke.test_false_monke
+                };
+                let target = troop.index[&target_monke_id];
+                // monkes later in the order still get their turn this round
+                if target <= monke_index {
+                    round += 1;
+                }
+                monke_index = target;
+            };
+
+            let cycle_start = match cycle_start {
+                Some(i) => i,
+                None => {
+                    // ran out of rounds before the item started repeating
+                    for &(monke_index, _) in &path {
+                        activity[monke_index] += 1;
+                    }
+                    continue;
+                }
+            };
+
+            for &(monke_index, _) in &path[..cycle_start] {
+                activity[monke_index] += 1;
+            }
+            // each lap around the cycle takes this many rounds, at least one
+            // since a monke can't get the same item back within a round
+            let cycle_rounds = round - path[cycle_start].1;
+            for &(monke_index, first_round) in &path[cycle_start..] {
+                // inspections happen in first_round, first_round + cycle_rounds, ...
+                if first_round < rounds {
+                    // saturating, for absurd amounts of rounds
+                    activity[monke_index] = activity[monke_index]
+                        .saturating_add((rounds - first_round).div_ceil(cycle_rounds));
+                }
+            }
+        }
+    }
+
+    activity
+}
+
+// the two most active monkes multiplied
+fn monke_business(activity: &[u64]) -> u128 {
+    let mut activity = activity.to_vec();
+    activity.sort();
+
+    let top_monkes: Vec<_> = activity.iter().rev().take(2).collect();
+    top_monkes.iter().map(|x| **x as u128).product()
+}
+
+fn evaluate(operation: &Operation, operand: i64) -> i64 {
+    match operation {
+        Operation::Old => operand,
+        Operation::Constant(x) => *x,
+        Operation::Add(lhs, rhs) => evaluate(lhs, operand) + evaluate(rhs, operand),
+        Operation::Subtract(lhs, rhs) => evaluate(lhs, operand) - evaluate(rhs, operand),
+        Operation::Multiply(lhs, rhs) => evaluate(lhs, operand) * evaluate(rhs, operand),
+    }
+}
+
+// same as evaluate, but None instead of overflowing
+fn evaluate_checked(operation: &Operation, operand: i64) -> Option<i64> {
+    match operation {
+        Operation::Old => Some(operand),
+        Operation::Constant(x) => Some(*x),
+        Operation::Add(lhs, rhs) => {
+            evaluate_checked(lhs, operand)?.checked_add(evaluate_checked(rhs, operand)?)
+        }
+        Operation::Subtract(lhs, rhs) => {
+            evaluate_checked(lhs, operand)?.checked_sub(evaluate_checked(rhs, operand)?)
+        }
+        Operation::Multiply(lhs, rhs) => {
+            evaluate_checked(lhs, operand)?.checked_mul(evaluate_checked(rhs, operand)?)
+        }
+    }
+}
+
+// same as evaluate, but can't overflow
+fn evaluate_exact(operation: &Operation, operand: &BigInt) -> BigInt {
+    match operation {
+        Operation::Old => operand.clone(),
+        Operation::Constant(x) => BigInt::from(*x),
+        Operation::Add(lhs, rhs) => evaluate_exact(lhs, operand) + evaluate_exact(rhs, operand),
+        Operation::Subtract(lhs, rhs) => {
+            evaluate_exact(lhs, operand) - evaluate_exact(rhs, operand)
+        }
+        Operation::Multiply(lhs, rhs) => {
+            evaluate_exact(lhs, operand) * evaluate_exact(rhs, operand)
+        }
+    }
+}
+
+// same as evaluate, but every intermediate result is reduced so nothing overflows
+fn evaluate_modulo(operation: &Operation, operand: i64, modulus: i64) -> i64 {
+    let reduce = |x: i128| x.rem_euclid(modulus as i128) as i64;
+    match operation {
+        Operation::Old => operand.rem_euclid(modulus),
+        Operation::Constant(x) => x.rem_euclid(modulus),
+        Operation::Add(lhs, rhs) => reduce(
+            evaluate_modulo(lhs, operand, modulus) as i128
+                + evaluate_modulo(rhs, operand, modulus) as i128,
+        ),
+        Operation::Subtract(lhs, rhs) => reduce(
+            evaluate_modulo(lhs, operand, modulus) as i128
+                - evaluate_modulo(rhs, operand, modulus) as i128,
+        ),
+        Operation::Multiply(lhs, rhs) => reduce(
+            evaluate_modulo(lhs, operand, modulus) as i128
+                * evaluate_modulo(rhs, operand, modulus) as i128,
+        ),
+    }
+}
+
+// expression tree for the right hand side of "new = ..."
+#[derive(Debug, PartialEq)]
+enum Operation {
+    Old,
+    Constant(i64),
+    Add(Box<Operation>, Box<Operation>),
+    Subtract(Box<Operation>, Box<Operation>),
+    Multiply(Box<Operation>, Box<Operation>),
+}
+
+impl Operation {
+    // binds tighter the higher it is, for putting parentheses back in
+    fn precedence(&self) -> u8 {
+        match self {
+            Operation::Old | Operation::Constant(_) => 2,
+            Operation::Multiply(_, _) => 1,
+            Operation::Add(_, _) | Operation::Subtract(_, _) => 0,
+        }
+    }
+}
+
+// the same text parse_operation reads, so it parses back into the same tree
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (lhs, symbol, rhs) = match self {
+            Operation::Old => return write!(f, "old"),
+            Operation::Constant(x) => return write!(f, "{x}"),
+            Operation::Add(lhs, rhs) => (lhs, "+", rhs),
+            Operation::Subtract(lhs, rhs) => (lhs, "-", rhs),
+            Operation::Multiply(lhs, rhs) => (lhs, "*", rhs),
+        };
+        // everything is left associative, so the right side needs parentheses
+        // already when it binds just as tight
+        if lhs.precedence() < self.precedence() {
+            write!(f, "({lhs})")?;
+        } else {
+            write!(f, "{lhs}")?;
+        }
+        write!(f, " {symbol} ")?;
+        if rhs.precedence() <= self.precedence() {
+            write!(f, "({rhs})")
+        } else {
+            write!(f, "{rhs}")
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Token {
+    Old,
+    Number(i64),
+    Plus,
+    Minus,
+    Star,
+    Open,
+    Close,
+}
+
+fn tokenize(src: &str) -> Result<Vec<Token>> {
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut chars = src.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            ' ' => {
+                chars.next();
+            }
+            '+' | '-' | '*' | '(' | ')' => {
+                chars.next();
+                tokens.push(match c {
+                    '+' => Token::Plus,
+                    '-' => Token::Minus,
+                    '*' => Token::Star,
+                    '(' => Token::Open,
+                    _ => Token::Close,
+                });
+            }
+            '0'..='9' => {
+                let mut number = String::new();
+                while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+                    number.push(digit);
+                }
+                tokens.push(Token::Number(number.parse()?));
+            }
+            'a'..='z' => {
+                let mut word = String::new();
+                while let Some(letter) = chars.next_if(char::is_ascii_lowercase) {
+                    word.push(letter);
+                }
+                if word != "old" {
+                    return Err(format!("unknown variable {word:?}").into());
+                }
+                tokens.push(Token::Old);
+            }
+            _ => return Err(format!("unexpected character {c:?} in operation").into()),
+        }
+    }
+    Ok(tokens)
+}
+
+const MAX_OPERATION_TOKENS: usize = 100;
+
+// Recursive descent with the usual precedence, * binds tighter than + and -:
+//   expression = term (("+" | "-") term)*
+//   term       = factor ("*" factor)*
+//   factor     = "old" | number | "(" expression ")"
+fn parse_operation(src: &str) -> Result<Operation> {
+    let tokens = tokenize(src)?;
+    // keeps the recursion here and when evaluating the tree shallow
+    if tokens.len() > MAX_OPERATION_TOKENS {
+        return Err(format!("operation longer than {MAX_OPERATION_TOKENS} tokens").into());
+    }
+    let mut position = 0;
+    let operation = parse_expression(&tokens, &mut position)?;
+    if position != tokens.len() {
+        return Err(format!("unexpected {:?} in operation", tokens[position]).into());
+    }
+    Ok(operation)
+}
+
+fn parse_expression(tokens: &[Token], position: &mut usize) -> Result<Operation> {
+    let mut lhs = parse_term(tokens, position)?;
+    while let Some(&token) = tokens.get(*position) {
+        let combine = match token {
+            Token::Plus => Operation::Add,
+            Token::Minus => Operation::Subtract,
+            _ => break,
+        };
+        *position += 1;
+        let rhs = parse_term(tokens, position)?;
+        lhs = combine(Box::new(lhs), Box::new(rhs));
+    }
+    Ok(lhs)
+}
+
+fn parse_term(tokens: &[Token], position: &mut usize) -> Result<Operation> {
+    let mut lhs = parse_factor(tokens, position)?;
+    while tokens.get(*position) == Some(&Token::Star) {
+        *position += 1;
+        let rhs = parse_factor(tokens, position)?;
+        lhs = Operation::Multiply(Box::new(lhs), Box::new(rhs));
+    }
+    Ok(lhs)
+}
+
+fn parse_factor(tokens: &[Token], position: &mut usize) -> Result<Operation> {
+    let token = tokens
+        .get(*position)
+        .ok_or("operation ended unexpectedly")?;
+    *position += 1;
+    match token {
+        Token::Old => Ok(Operation::Old),
+        Token::Number(x) => Ok(Operation::Constant(*x)),
+        Token::Open => {
+            let inner = parse_expression(tokens, position)?;
+            if tokens.get(*position) != Some(&Token::Close) {
+                return Err("missing closing parenthesis".into());
+            }
+            *position += 1;
+            Ok(inner)
+        }
+        _ => Err(format!("unexpected {token:?} in operation").into()),
+    }
+}
+
+// (sic)
+#[derive(Debug)]
+struct Monke {
+    id: u32,
+    operation: Operation,
+    test_divisible: i64,
+    test_true_monke: u32,
+    test_false_monke: u32,
+}
+
+// All monkeys sorted by id, which is also the order they take their turns in.
+struct Troop {
+    monkes: Vec<Monke>,
+    // starting items, in the same order as monkes
+    items: Vec<Vec<i64>>,
+    // monke id -> index in monkes
+    index: HashMap<u32, usize>,
+    // least common multiple of all test divisors
+    modulus: i64,
+}
+
+// None if the least common multiple doesn't fit in i64
+fn divisor_lcm(monkes: &[Monke]) -> Option<i64> {
+    let gcd = |mut a: i64, mut b: i64| {
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        a
+    };
+    monkes.iter().try_fold(1i64, |lcm, m| {
+        (lcm / gcd(lcm, m.test_divisible)).checked_mul(m.test_divisible)
+    })
+}
+
+fn parse_troop(input: &str) -> Result<Troop> {
+    let mut parsed: Vec<(Monke, Vec<i64>)> = Vec::new();
+    for monke_str in input.split("\n\n") {
+        parsed.push(parse_monke(monke_str)?);
+    }
+    validate_troop(&parsed)?;
+
+    parsed.sort_by_key(|(monke, _)| monke.id);
+    let (monkes, items): (Vec<_>, Vec<_>) = parsed.into_iter().unzip();
+    let index = monkes
+        .iter()
+        .enumerate()
+        .map(|(i, monke)| (monke.id, i))
+        .collect();
+    let modulus = divisor_lcm(&monkes).ok_or("the test divisors have no common multiple in i64")?;
+    Ok(Troop {
+        monkes,
+        items,
+        index,
+        modulus,
+    })
+}
+
+// Checks that every throw lands on an existing monke, and reports every
+// problem at once instead of stopping at the first one.
+fn validate_troop(parsed: &[(Monke, Vec<i64>)]) -> Result<()> {
+    let mut problems: Vec<String> = Vec::new();
+
+    let mut ids: HashSet<u32> = HashSet::new();
+    for (monke, _) in parsed {
+        if !ids.insert(monke.id) {
+            problems.push(format!("monkey {} is declared more than once", monke.id));
+        }
+    }
+
+    for (monke, _) in parsed {
+        if monke.test_divisible <= 0 {
+            problems.push(format!(
+                "monkey {} tests divisibility by {}",
+                monke.id, monke.test_divisible
+            ));
+        }
+        for target in [monke.test_true_monke, monke.test_false_monke] {
+            if target == monke.id {
+                problems.push(format!("monkey {} throws to itself", monke.id));
+            } else if !ids.contains(&target) {
+                problems.push(format!(
+                    "monkey {} throws to monkey {target}, which doesn't exist",
+                    monke.id
+                ));
+            }
+        }
+    }
+
+    // items start from the monkes holding them and can only go where they're thrown
+    let mut reachable: HashSet<u32> = HashSet::new();
+    let mut queue: Vec<u32> = parsed
+        .iter()
+        .filter(|(_, items)| !items.is_empty())
+        .map(|(monke, _)| monke.id)
+        .collect();
+    while let Some(id) = queue.pop() {
+        if !reachable.insert(id) {
+            continue;
+        }
+        for (monke, _) in parsed.iter().filter(|(monke, _)| monke.id == id) {
+            queue.push(monke.test_true_monke);
+            queue.push(monke.test_false_monke);
+        }
+    }
+    for (monke, _) in parsed {
+        if !reachable.contains(&monke.id) {
+            problems.push(format!("monkey {} never gets any items", monke.id));
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("invalid monkeys:\n  {}", problems.join("\n  ")).into())
+    }
+}
+
+fn parse_monke(src: &str) -> Result<(Monke, Vec<i64>)> {
+    lazy_static! {
+        static ref RE_ID: Regex = Regex::new(r"Monkey (\d+):").unwrap();
+        static ref RE_ITEMS: Regex = Regex::new(r"items: (.+)$").unwrap();
+    }
+    let mut lines = src.lines();
+
+    let id: u32 = RE_ID
+        .captures(lines.next().ok_or("missing line")?)
+        .ok_or("id not found")?[1]
+        .parse()?;
+
+    let items: Vec<i64> = RE_ITEMS
+        .captures(lines.next().ok_or("missing line")?)
+        .ok_or("items not found")?[1]
+        .split(", ")
+        .map(|item| {
+            item.parse()
+                .map_err(|_| format!("invalid item {item:?}").into())
+        })
+        .collect::<Result<_>>()?;
+
+    let (_, operation_src) = lines
+        .next()
+        .ok_or("missing line")?
+        .split_once("new =")
+        .ok_or("operation not found")?;
+    let operation = parse_operation(operation_src)?;
+
+    let test_divisible: i64 = line_after(&mut lines, "Test: divisible by ")?.parse()?;
+    let test_true_monke: u32 = line_after(&mut lines, "If true: throw to monkey ")?.parse()?;
+    let test_false_monke: u32 = line_after(&mut lines, "If false: throw to monkey ")?.parse()?;
+    if let Some(line) = lines.next() {
+        return Err(format!("unexpected line {line:?}").into());
+    }
+
+    let monke = Monke {
+        id,
+        operation,
+        test_divisible,
+        test_true_monke,
+        test_false_monke,
+    };
+    Ok((monke, items))
+}
+
+// the rest of the next line, which has to start with prefix after the indentation
+fn line_after<'a>(lines: &mut std::str::Lines<'a>, prefix: &str) -> Result<&'a str> {
+    let line = lines.next().ok_or("missing line")?;
+    line.trim_start()
+        .strip_prefix(prefix)
+        .ok_or_else(|| format!("expected {prefix:?}, got {line:?}").into())
+}
+
+// Size is the amount of monkeys, at least two. Troops whose worry levels
+// would overflow i64 in part 1 are thrown away and generated again.
+fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    loop {
+        let input = generate_troop(&mut rng, size.max(2));
+        let troop = match parse_troop(&input) {
+            Ok(troop) => troop,
+            Err(_) => continue,
+        };
+        if simulate_checked(&troop, 20).is_ok() {
+            return input;
+        }
+    }
+}
+
+fn generate_troop(rng: &mut Rng, monke_count: usize) -> String {
+    // the product of all of these still fits in i64, so the modulus does too
+    const PRIMES: [i64; 15] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47];
+    let mut monkes: Vec<String> = Vec::new();
+
+    for id in 0..monke_count {
+        // every monke holds something, so every monke gets items
+        let items: Vec<String> = (0..rng.range(1..=4))
+            .map(|_| rng.range(50..=99).to_string())
+            .collect();
+        let operation = match rng.below(10) {
+            0 => String::from("old * old"),
+            1..=5 => format!("old + {}", rng.range(1..=9)),
+            _ => format!("old * {}", rng.range(2..=19)),
+        };
+        let mut target = || (id + 1 + rng.below(monke_count as u64 - 1) as usize) % monke_count;
+        let (if_true, if_false) = (target(), target());
+        let lines = [
+            format!("Monkey {id}:"),
+            format!("  Starting items: {}", items.join(", ")),
+            format!("  Operation: new = {operation}"),
+            format!("  Test: divisible by {}", rng.choose(&PRIMES)),
+            format!("    If true: throw to monkey {if_true}"),
+            format!("    If false: throw to monkey {if_false}"),
+        ];
+        monkes.push(lines.join("\n"));
+    }
+
+    monkes.join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("../examples/example.txt");
+
+    // example_* tests, generated by build.rs from examples/manifest
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+
+    #[test]
+    fn operation_test() -> Result<()> {
+        use Operation::*;
+
+        assert_eq!(
+            parse_operation("old * 19")?,
+            Multiply(Box::new(Old), Box::new(Constant(19)))
+        );
+        let cases = [
+            ("old + 6", 10, 16),
+            ("old * old", 10, 100),
+            ("old + old", 10, 20),
+            ("old - 3", 10, 7),
+            ("(old * 2) + 1", 10, 21),
+            ("old * 2 + 1", 10, 21),
+            ("1 + old * 2", 10, 21),
+            ("(1 + old) * 2", 10, 22),
+            ("old - 2 - 3", 10, 5),
+            ("((old))", 10, 10),
+        ];
+        for (src, old, expected) in cases {
+            assert_eq!(evaluate(&parse_operation(src)?, old), expected, "{src}");
+        }
+
+        for src in [
+            "", "old +", "(old * 2", "old * 2)", "new + 1", "old / 2", "old old",
+        ] {
+            assert!(parse_operation(src).is_err(), "{src}");
+        }
+        // deep nesting would overflow the stack
+        let nested = format!("{}old{}", "(".repeat(100000), ")".repeat(100000));
+        assert!(parse_operation(&nested).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn validate_test() {
+        let monke = |id: u32, item_count: usize, test_true_monke: u32, test_false_monke: u32| {
+            let monke = Monke {
+                id,
+                operation: Operation::Old,
+                test_divisible: 2,
+                test_true_monke,
+                test_false_monke,
+            };
+            (monke, vec![1; item_count])
+        };
+
+        assert!(validate_troop(&[monke(0, 1, 1, 2), monke(1, 0, 2, 0), monke(2, 0, 0, 1)]).is_ok());
+
+        let troop = [
+            monke(0, 1, 1, 0),
+            monke(1, 0, 7, 0),
+            monke(1, 0, 0, 0),
+            monke(3, 0, 0, 1),
+        ];
+        let error = validate_troop(&troop).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            concat!(
+                "invalid monkeys:\n",
+                "  monkey 1 is declared more than once\n",
+                "  monkey 0 throws to itself\n",
+                "  monkey 1 throws to monkey 7, which doesn't exist\n",
+                "  monkey 3 never gets any items",
+            )
+        );
+    }
+
+    #[test]
+    fn troop_order_test() -> Result<()> {
+        // turns go by id, not by the order in the input
+        let input = common::normalize(INPUT);
+        let mut parts: Vec<&str> = input.split("\n\n").collect();
+        parts.reverse();
+        let reversed = parts.join("\n\n");
+        let troop = parse_troop(&reversed)?;
+        let ids: Vec<u32> = troop.monkes.iter().map(|m| m.id).collect();
+        assert_eq!(ids, vec![0, 1, 2, 3]);
+        assert_eq!(part1(&reversed)?, "10605");
+
+        Ok(())
+    }
+
+    #[test]
+    fn cycles_test() -> Result<()> {
+        let troop = parse_troop(INPUT)?;
+        // numbers from the puzzle text
+        assert_eq!(count_inspections_by_cycles(&troop, 1), vec![2, 4, 3, 6]);
+        assert_eq!(
+            count_inspections_by_cycles(&troop, 20),
+            vec![99, 97, 8, 103]
+        );
+        for rounds in [0, 1, 2, 3, 19, 1000, 10000] {
+            assert_eq!(
+                count_inspections_by_cycles(&troop, rounds),
+                simulate::<i64>(&troop, rounds, Relief::None),
+                "{rounds}"
+            );
+        }
+        // way too many rounds to simulate, but every monke keeps inspecting
+        let fewer = count_inspections_by_cycles(&troop, 1_000_000_000_000);
+        let more = count_inspections_by_cycles(&troop, 2_000_000_000_000);
+        for (fewer, more) in fewer.iter().zip(&more) {
+            assert!(fewer * 2 - 100 <= *more && *more <= fewer * 2 + 100);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn exact_test() -> Result<()> {
+        let troop = parse_troop(INPUT)?;
+        assert_eq!(
+            simulate::<BigInt>(&troop, 20, Relief::DivideByThree),
+            simulate::<i64>(&troop, 20, Relief::DivideByThree)
+        );
+        // worry levels get squared over and over, so keep it short
+        for rounds in 0..=10 {
+            assert_eq!(
+                simulate::<BigInt>(&troop, rounds, Relief::None),
+                simulate::<i64>(&troop, rounds, Relief::None),
+                "{rounds}"
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn evaluate_exact_test() -> Result<()> {
+        let sources = [
+            "old",
+            "7",
+            "old + 6",
+            "old + old",
+            "old - 3",
+            "3 - old",
+            "old * 19",
+            "old * old",
+            "(old * 2) + 1",
+            "old * (old - 4) * old",
+        ];
+        for src in sources {
+            let operation = parse_operation(src)?;
+            for operand in -50..=50 {
+                let exact = evaluate_exact(&operation, &BigInt::from(operand));
+                assert_eq!(exact, BigInt::from(evaluate(&operation, operand)), "{src}");
+                for modulus in [1, 2, 23, 96577] {
+                    let reduced = ((&exact % modulus) + modulus) % modulus;
+                    assert_eq!(
+                        reduced,
+                        BigInt::from(evaluate_modulo(&operation, operand, modulus)),
+                        "{src} mod {modulus}"
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn inspection_log_test() -> Result<()> {
+        let troop = parse_troop(INPUT)?;
+        let log = inspection_log(&troop, 20, Relief::DivideByThree, &[1, 20]);
+        // from the puzzle text
+        let expected = concat!(
+            "Monkey 0:\n",
+            "  Monkey inspects an item with a worry level of 79.\n",
+            "    Worry level is multiplied by 19 to 1501.\n",
+            "    Monkey gets bored with item. Worry level is divided by 3 to 500.\n",
+            "    Current worry level is not divisible by 23.\n",
+            "    Item with worry level 500 is thrown to monkey 3.\n",
+            "  Monkey inspects an item with a worry level of 98.\n",
+            "    Worry level is multiplied by 19 to 1862.\n",
+            "    Monkey gets bored with item. Worry level is divided by 3 to 620.\n",
+            "    Current worry level is not divisible by 23.\n",
+            "    Item with worry level 620 is thrown to monkey 3.\n",
+            "Monkey 1:\n",
+        );
+        assert!(log.starts_with(expected), "{log}");
+        assert!(log.contains(concat!(
+            "  Monkey inspects an item with a worry level of 79.\n",
+            "    Worry level is multiplied by itself to 6241.\n",
+        )));
+        assert!(log.contains(concat!(
+            "After round 1, the monkeys are holding items with these worry levels:\n",
+            "Monkey 0: 20, 23, 27, 26\n",
+            "Monkey 1: 2080, 25, 167, 207, 401, 1046\n",
+            "Monkey 2: \n",
+            "Monkey 3: \n",
+        )));
+        assert!(log.contains("Monkey 0: 10, 12, 14, 26, 34\n"));
+        assert!(log.contains("Monkey 3 inspected items 105 times.\n"));
+        assert!(!log.contains("After round 2,"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn activity_csv_test() -> Result<()> {
+        let troop = parse_troop(INPUT)?;
+        let csv = activity_csv(&troop, 20, Relief::DivideByThree);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 1 + 21 * 4);
+        assert_eq!(lines[0], "round,monkey,item_count,items,activity");
+        assert_eq!(lines[1], "0,0,2,79 98,0");
+        assert_eq!(lines[5], "1,0,4,20 23 27 26,2");
+        assert_eq!(lines[7], "1,2,0,,3");
+        assert_eq!(lines[84], "20,3,0,,105");
+
+        Ok(())
+    }
+
+    #[test]
+    fn display_operation_test() -> Result<()> {
+        let sources = [
+            "old * 19",
+            "old * old",
+            "old + old - 3",
+            "old - (old - 3)",
+            "old * 2 + 1",
+            "(old + 2) * 3",
+            "old * (old * 2)",
+        ];
+        for src in sources {
+            let operation = parse_operation(src)?;
+            assert_eq!(operation.to_string(), src);
+            assert_eq!(parse_operation(&operation.to_string())?, operation);
+        }
+        assert_eq!(parse_operation("(old * 2) + 1")?.to_string(), "old * 2 + 1");
+
+        Ok(())
+    }
+
+    #[test]
+    fn generate_test() -> Result<()> {
+        let input = generate(1, 8);
+        assert_eq!(input, generate(1, 8));
+        assert_ne!(input, generate(2, 8));
+        part1(&input)?;
+        part2(&input)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn generated_troop_test() -> Result<()> {
+        for seed in 0..10 {
+            let troop = parse_troop(&generate(seed, seed as usize + 2))?;
+            assert_eq!(
+                simulate::<Checked>(&troop, 20, Relief::DivideByThree),
+                simulate::<i64>(&troop, 20, Relief::DivideByThree)
+            );
+            for rounds in [1, 5, 100] {
+                assert_eq!(
+                    count_inspections_by_cycles(&troop, rounds),
+                    simulate::<i64>(&troop, rounds, Relief::None)
+                );
+            }
+            for rounds in [1, 3] {
+                assert_eq!(
+                    simulate::<BigInt>(&troop, rounds, Relief::None),
+                    simulate::<i64>(&troop, rounds, Relief::None)
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn overflow_test() {
+        // part 1 squares 60 twice per round without any modulo
+        let squares = INPUT.replace("old + 3", "old * old");
+        assert!(part1(&squares).is_err());
+        assert!(part2(&squares).is_ok());
+        let large_divisors = INPUT
+            .replace("by 23", "by 3037000493")
+            .replace("by 19", "by 3037000453");
+        assert!(parse_troop(&large_divisors).is_err());
+    }
+
+    #[test]
+    fn parse_monke_test() {
+        let monke = common::normalize(INPUT.split("\n\n").next().unwrap_or_default());
+        assert!(parse_monke(&monke).is_ok());
+        for (from, to) in [
+            ("79, 98", "79, x98"),
+            ("79, 98", "79,98"),
+            ("divisible by", "divisible at"),
+            ("If true", "If false"),
+            ("throw to monkey 3", "throw to monkey 3\nMonkey 4:"),
+        ] {
+            let broken = monke.replace(from, to);
+            assert!(parse_monke(&broken).is_err(), "{broken}");
+        }
+    }
+
+    // one fuzz target for each parser, from the smallest up
+
+    #[test]
+    fn fuzz_parse_operation_test() {
+        let seeds = [
+            "old * 19",
+            "old * old",
+            "(old + 2) * 3 - old",
+            "1 - (2 - old)",
+        ];
+        common::fuzz::fuzz(&seeds, |src| {
+            if let Ok(operation) = parse_operation(src) {
+                evaluate_checked(&operation, 79);
+                evaluate_modulo(&operation, 79, 96577);
+                evaluate_exact(&operation, &BigInt::from(79));
+                let displayed = operation.to_string();
+                assert_eq!(
+                    parse_operation(&displayed).ok(),
+                    Some(operation),
+                    "{displayed}"
+                );
+            }
+        });
+    }
+
+    #[test]
+    fn fuzz_parse_monke_test() {
+        let input = common::normalize(INPUT);
+        let seeds: Vec<&str> = input.split("\n\n").collect();
+        common::fuzz::fuzz(&seeds, |src| {
+            let _ = parse_monke(src);
+        });
+    }
+
+    #[test]
+    fn fuzz_parse_troop_test() {
+        common::fuzz::fuzz(&[INPUT, &generate(0, 4)], |input| {
+            let _ = part1(input);
+            if let Ok(troop) = parse_troop(input) {
+                simulate::<i64>(&troop, 100, Relief::None);
+                count_inspections_by_cycles(&troop, 100);
+                inspection_log(&troop, 2, Relief::DivideByThree, &[1, 2]);
+                activity_csv(&troop, 2, Relief::None);
+            }
+        });
+    }
+}